@@ -1,14 +1,39 @@
-use std::fs;
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use ratatui::{style::Color, widgets::ListState};
 
-use crate::ui::maze_ui::MazeView;
+use crate::{
+    backend::{BuiltinBackend, MazeBackend},
+    browser::FileBrowser,
+    export,
+    job::Job,
+    maze::Maze,
+    save::{self, Metadata, Save, SaveKind},
+    session::Session,
+    setup::Setup,
+    steps::{StepFile, Steps},
+    ui::maze_ui::{MazeView, RenderMode, Viewport},
+};
+
+/// The largest width or height a maze can be given.
+pub const MAX_SIZE: usize = 1000;
 
 pub enum CurrentScreen {
     Main,
     Size,
     Speed,
+    Seed,
     Algorithm,
+    Setup,
+    Open,
+    Save,
+    Restore,
 }
 
 #[derive(PartialEq, Clone)]
@@ -68,20 +93,42 @@ pub enum AlgorithmSetting {
     Solver,
 }
 
+// What was on screen before a run started, restored if the run doesn't finish.
+struct Stash {
+    maze: Maze,
+    maze_steps: Steps,
+    step: usize,
+}
+
 pub struct App {
     pub current_screen: CurrentScreen,
     pub size_setting: SizeSetting,
     pub algorithm_setting: AlgorithmSetting,
     pub tmp: usize,
+    pub seed: Option<u64>,
+    pub seed_tmp: Option<u64>,
+    pub last_seed: Option<u64>,
+    pub last_generator: Option<String>,
+    pub last_solver: Option<String>,
     pub default_color: Color,
     pub highlight_fg: Color,
     pub highlight_bg: Color,
-    pub gen_bin: String,
-    pub solve_bin: String,
-    pub maze: String,
-    pub maze_steps: Vec<String>,
+    pub backends: Vec<Arc<dyn MazeBackend>>,
+    pub kept_dirs: Vec<PathBuf>,
+    pub setup: Option<Setup>,
+    pub browser: Option<FileBrowser>,
+    pub save: Option<Save>,
+    pub session: Option<Session>,
+    pub job: Option<Job>,
+    pub error: Option<String>,
+    pub maze: Maze,
+    pub generated_maze: Option<Maze>,
+    pub maze_steps: Steps,
     pub has_generated: bool,
+    pub playing: bool,
     pub maze_veiwer: MazeView,
+    pub viewport: Viewport,
+    pub render_mode: RenderMode,
     pub gen_algorithm: GenAlgorithms,
     pub solve_algorithm: SolveAlgorithms,
     pub gen_list_state: ListState,
@@ -90,10 +137,13 @@ pub struct App {
     pub solve_algo_lookup: Vec<SolveAlgorithms>,
     width: usize,
     height: usize,
-    max_size: usize,
+    backend: usize,
     ratio: f64,
     step: usize,
     speed: usize,
+    following: bool,
+    loading_steps: bool,
+    stash: Option<Stash>,
 }
 
 impl App {
@@ -103,16 +153,35 @@ impl App {
             size_setting: SizeSetting::Width,
             algorithm_setting: AlgorithmSetting::Generator,
             tmp: 2,
+            seed: None,
+            seed_tmp: None,
+            last_seed: None,
+            last_generator: None,
+            last_solver: None,
             default_color: Color::White,
             highlight_fg: Color::Black,
             highlight_bg: Color::Yellow,
-            gen_bin: "".to_string(),
-            solve_bin: "".to_string(),
-            maze: "".to_string(),
-            maze_steps: Vec::with_capacity(0),
+            backends: Vec::from([Arc::new(BuiltinBackend) as Arc<dyn MazeBackend>]),
+            kept_dirs: Vec::new(),
+            setup: None,
+            browser: None,
+            save: None,
+            session: None,
+            job: None,
+            error: None,
+            backend: 0,
+            maze: Maze::default(),
+            generated_maze: None,
+            maze_steps: Steps::default(),
             speed: 50,
             has_generated: false,
+            playing: false,
+            following: false,
+            loading_steps: false,
+            stash: None,
             maze_veiwer: MazeView::new(),
+            viewport: Viewport::new(),
+            render_mode: RenderMode::Auto,
             gen_algorithm: GenAlgorithms::Kruskal,
             solve_algorithm: SolveAlgorithms::Depth,
             gen_list_state: ListState::default().with_selected(Some(0)),
@@ -139,50 +208,216 @@ impl App {
             width: 2,
             height: 2,
             ratio: 0.5,
-            max_size: 2,
             step: 0,
         }
     }
 
     pub fn clear_maze(&mut self) {
-        let wall_row = String::from("#".repeat(2 * self.width + 1) + "\n");
-        let cell_row = String::from("# ".repeat(self.width) + "#\n");
-        let mut maze = String::with_capacity((2 * self.width + 2) * (2 * self.height + 1) + 1);
+        self.maze = Maze::closed(self.width, self.height);
+        self.has_generated = false;
+        self.last_seed = None;
+        self.last_generator = None;
+        self.last_solver = None;
+    }
+
+    /// Starts collecting the steps of a new run. Until the first steps arrive the view
+    /// follows the newest one.
+    pub fn begin_run(&mut self) {
+        self.stash = Some(Stash {
+            maze: self.maze.clone(),
+            maze_steps: self.maze_steps.take(),
+            step: self.step,
+        });
+        self.step = 0;
+        self.following = true;
+    }
+
+    pub fn push_step(&mut self, step: Maze) {
+        self.maze_steps.push(step);
+
+        if self.following {
+            self.step = self.maze_steps.len() - 1;
+            self.show_step();
+        }
+    }
+
+    pub fn finish_run(&mut self, maze: Maze) {
+        self.stash = None;
 
-        for _ in 0..self.height {
-            maze.push_str(&wall_row);
-            maze.push_str(&cell_row);
+        if self.following {
+            self.viewport.track(&self.maze, &maze);
+            self.maze = maze;
+            self.step = 0;
         }
-        maze.push_str(&wall_row);
-        maze.pop();
 
-        self.maze = maze;
-        self.has_generated = false;
+        self.following = false;
+    }
+
+    /// Puts back whatever was shown before the run started.
+    pub fn abandon_run(&mut self) {
+        if let Some(stash) = self.stash.take() {
+            self.maze = stash.maze;
+            self.maze_steps = stash.maze_steps;
+            self.step = stash.step;
+        }
+
+        self.following = false;
+        self.playing = false;
+    }
+
+    /// Shows a step file, or a maze file as a single step. The file is indexed in the
+    /// background, see [`App::poll_steps`].
+    pub fn open_file(&mut self, path: &Path) -> io::Result<()> {
+        self.open_file_at(path, 0)
+    }
+
+    /// Like [`App::open_file`], but starts at `step` once the file has that many.
+    pub fn open_file_at(&mut self, path: &Path, step: usize) -> io::Result<()> {
+        self.maze_steps = Steps::File(StepFile::open(path)?);
+        self.step = step;
+        self.playing = false;
+        self.loading_steps = true;
+        self.has_generated = true;
+        self.generated_maze = None;
+        self.last_seed = None;
+        self.last_generator = None;
+        self.last_solver = None;
+
+        Ok(())
+    }
+
+    /// Keeps up with a step file that is still being indexed. Shows the step to start at
+    /// once it is found, and makes the last one solvable once the scan is done.
+    pub fn poll_steps(&mut self) {
+        let Steps::File(file) = &self.maze_steps else {
+            return;
+        };
+
+        if let Some(err) = file.take_error() {
+            self.error = Some(format!("Failed to read the step file.\n\n{err}"));
+        }
+
+        if self.generated_maze.is_none() && file.is_indexed() {
+            self.generated_maze = self.maze_steps.last();
+        }
+
+        let found = self.maze_steps.len() > self.step || file.is_indexed();
+
+        if self.loading_steps && found && !self.maze_steps.is_empty() {
+            self.loading_steps = false;
+            self.set_step_val(self.step);
+            self.show_step();
+            self.set_width(self.maze.width());
+            self.set_height(self.maze.height());
+        }
     }
 
-    pub fn load_steps(&mut self, arg: &str) {
-        let str = fs::read_to_string(arg).expect("Failed to read: {arg}");
+    /// Describes the last run, for the header of saved files.
+    pub fn metadata(&self) -> Metadata {
+        Metadata {
+            generator: self.last_generator.clone(),
+            seed: self.last_seed,
+            solver: self.last_solver.clone(),
+        }
+    }
 
-        // replace CRLF with just LF if they exist (only on Windows)
-        let str = str.replace("\r\n\r\n", "\n\n");
+    /// Writes the generated maze, every step, the frame on screen, or an export of the
+    /// steps to `path`.
+    pub fn save_file(&self, kind: SaveKind, path: &Path) -> io::Result<()> {
+        match kind {
+            SaveKind::Maze => match &self.generated_maze {
+                Some(maze) => save::save_maze(path, maze, &self.metadata()),
+                None => Err(io::Error::other("there is no generated maze to save")),
+            },
+            SaveKind::Steps | SaveKind::Cast | SaveKind::Gif if self.is_indexing() => Err(
+                io::Error::other("the step file is still being read, try again once it is done"),
+            ),
+            SaveKind::Steps => save::save_steps(path, &self.maze_steps, &self.metadata()),
+            SaveKind::Frame => save::save_maze(path, &self.maze, &self.metadata()),
+            SaveKind::Cast => export::write_cast(
+                BufWriter::new(File::create(path)?),
+                &self.maze_steps,
+                &self.maze_veiwer,
+                self.viewport.mode(),
+                self.get_period(),
+            ),
+            SaveKind::Gif => export::write_gif(
+                BufWriter::new(File::create(path)?),
+                &self.maze_steps,
+                &self.maze_veiwer,
+                self.get_period(),
+            ),
+            SaveKind::Svg => export::write_svg(
+                BufWriter::new(File::create(path)?),
+                &self.maze,
+                &self.maze_veiwer,
+            ),
+        }
+    }
 
-        self.maze_steps = str.split("\n\n").map(|s| s.to_string()).collect();
+    /// Whether a step file is still being scanned, or its first step is found but not
+    /// shown yet.
+    pub fn is_indexing(&self) -> bool {
+        (self.loading_steps && !self.maze_steps.is_empty())
+            || matches!(&self.maze_steps, Steps::File(file) if !file.is_indexed())
+    }
+
+    /// Shows the current step, reporting a step that can't be read.
+    pub fn show_step(&mut self) {
+        match self.get_step() {
+            Ok(step) => {
+                self.viewport.track(&self.maze, &step);
+                self.maze = step;
+            }
+            Err(err) => {
+                self.error = Some(format!("Failed to read step {}.\n\n{err}", self.step));
+                self.playing = false;
+            }
+        }
+    }
+
+    pub fn stop_following(&mut self) {
+        self.following = false;
+    }
+
+    pub fn can_step(&self) -> bool {
+        (self.has_generated || self.stash.is_some()) && !self.maze_steps.is_empty()
+    }
+
+    pub fn step_forward(&mut self) -> bool {
+        if self.step + 1 >= self.maze_steps.len() {
+            return false;
+        }
+
+        self.step += 1;
+        self.show_step();
+        true
+    }
+
+    pub fn step_back(&mut self) -> bool {
+        if self.step == 0 || self.maze_steps.is_empty() {
+            return false;
+        }
+
+        self.step -= 1;
+        self.show_step();
+        true
     }
 
     pub fn set_width(&mut self, size: usize) {
-        self.width = size.clamp(2, self.max_size)
+        self.width = size.clamp(2, MAX_SIZE)
     }
 
     pub fn set_height(&mut self, size: usize) {
-        self.height = size.clamp(2, self.max_size)
+        self.height = size.clamp(2, MAX_SIZE)
     }
 
-    pub fn set_max_size(&mut self, size: usize) {
-        self.max_size = size.max(2)
+    pub fn set_step_val(&mut self, step: usize) {
+        self.step = step.min(self.maze_steps.len().saturating_sub(1))
     }
 
-    pub fn set_step_val(&mut self, step: usize) {
-        self.step = step.clamp(0, self.maze_steps.len() - 1)
+    pub fn set_backend(&mut self, backend: usize) {
+        self.backend = backend % self.backends.len()
     }
 
     pub fn set_speed(&mut self, speed: usize) {
@@ -201,22 +436,36 @@ impl App {
         self.height
     }
 
-    pub fn get_max_size(&self) -> usize {
-        self.max_size
-    }
-
     pub fn get_step_val(&self) -> usize {
         self.step
     }
 
-    pub fn get_step(&self) -> &String {
-        &self.maze_steps[self.step]
+    pub fn get_step(&self) -> io::Result<Maze> {
+        self.maze_steps.get(self.step)
+    }
+
+    pub fn get_backend_val(&self) -> usize {
+        self.backend
+    }
+
+    pub fn get_backend(&self) -> Arc<dyn MazeBackend> {
+        self.backends[self.backend].clone()
     }
 
     pub fn get_speed(&self) -> usize {
         self.speed
     }
 
+    /// The seed shown in the header. An unset seed shows the one the current maze was
+    /// randomly generated with, if any.
+    pub fn get_seed_name(&self) -> String {
+        match (self.seed, self.last_seed) {
+            (Some(seed), _) => seed.to_string(),
+            (None, Some(seed)) => format!("{seed} (random)"),
+            (None, None) => "random".to_string(),
+        }
+    }
+
     pub fn get_period(&self) -> u64 {
         u64::try_from(1000 / self.speed).unwrap()
     }
@@ -227,6 +476,20 @@ impl App {
 }
 
 impl GenAlgorithms {
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            GenAlgorithms::GrowingTree(method) => {
+                let mut args = Vec::from(["Growing-Tree".to_string()]);
+                args.extend(method.to_args());
+                args
+            }
+            GenAlgorithms::BinaryTree(bias) => {
+                Vec::from(["Binary-Tree".to_string(), bias.to_string()])
+            }
+            _ => Vec::from([self.to_string()]),
+        }
+    }
+
     pub fn get_name(&self) -> String {
         match self {
             GenAlgorithms::Kruskal => "Kruskal".to_string(),
@@ -242,9 +505,70 @@ impl GenAlgorithms {
             GenAlgorithms::BinaryTree(bias) => "Binary-Tree ".to_string() + &bias.to_string(),
         }
     }
+
+    /// Finds an algorithm by its command line or display name, ignoring case, spacing and
+    /// punctuation. Growing tree methods that mix two choices take `ratio`.
+    pub fn from_name(name: &str, ratio: f64) -> Option<GenAlgorithms> {
+        let mut all = Vec::from([
+            GenAlgorithms::Kruskal,
+            GenAlgorithms::Prim,
+            GenAlgorithms::Back,
+            GenAlgorithms::AldousBroder,
+            GenAlgorithms::HuntAndKill,
+            GenAlgorithms::Wilson,
+            GenAlgorithms::Eller,
+            GenAlgorithms::Divide,
+            GenAlgorithms::Sidewinder,
+        ]);
+        all.extend(
+            [
+                TreeSubAlgorithm::Newest,
+                TreeSubAlgorithm::Middle,
+                TreeSubAlgorithm::Oldest,
+                TreeSubAlgorithm::Random,
+                TreeSubAlgorithm::NewestMiddle(ratio),
+                TreeSubAlgorithm::NewestOldest(ratio),
+                TreeSubAlgorithm::NewestRandom(ratio),
+                TreeSubAlgorithm::MiddleOldest(ratio),
+                TreeSubAlgorithm::MiddleRandom(ratio),
+                TreeSubAlgorithm::OldestRandom(ratio),
+            ]
+            .map(GenAlgorithms::GrowingTree),
+        );
+        all.extend(
+            [
+                BiasMethods::NorthWest,
+                BiasMethods::NorthEast,
+                BiasMethods::SouthWest,
+                BiasMethods::SouthEast,
+            ]
+            .map(GenAlgorithms::BinaryTree),
+        );
+
+        let name = name_key(name);
+
+        all.into_iter().find(|algorithm| {
+            name_key(&algorithm.to_string()) == name || name_key(&algorithm.get_name()) == name
+        })
+    }
 }
 
 impl TreeSubAlgorithm {
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            TreeSubAlgorithm::Newest
+            | TreeSubAlgorithm::Middle
+            | TreeSubAlgorithm::Oldest
+            | TreeSubAlgorithm::Random => Vec::from([self.to_string()]),
+            TreeSubAlgorithm::NewestMiddle(ratio) => ratio_args("Newest-Middle", *ratio),
+            TreeSubAlgorithm::NewestOldest(ratio) => ratio_args("Newest-Oldest", *ratio),
+            TreeSubAlgorithm::NewestRandom(ratio) => ratio_args("Newest-Random", *ratio),
+            TreeSubAlgorithm::MiddleOldest(ratio) => ratio_args("Middle-Oldest", *ratio),
+            TreeSubAlgorithm::MiddleRandom(ratio) => ratio_args("Middle-Random", *ratio),
+            TreeSubAlgorithm::OldestRandom(ratio) => ratio_args("Oldest-Random", *ratio),
+        }
+    }
+
     pub fn get_name(&self) -> String {
         match self {
             TreeSubAlgorithm::Newest => "Newest".to_string(),
@@ -274,6 +598,10 @@ impl TreeSubAlgorithm {
 }
 
 impl SolveAlgorithms {
+    pub fn to_args(&self) -> Vec<String> {
+        Vec::from([self.to_string()])
+    }
+
     pub fn get_name(&self) -> String {
         match self {
             SolveAlgorithms::Depth => "Depth First".to_string(),
@@ -282,63 +610,90 @@ impl SolveAlgorithms {
             SolveAlgorithms::AStar => "A-Star".to_string(),
         }
     }
+
+    /// Finds a solver by its command line or display name, like
+    /// [`GenAlgorithms::from_name`].
+    pub fn from_name(name: &str) -> Option<SolveAlgorithms> {
+        let name = name_key(name);
+
+        [
+            SolveAlgorithms::Depth,
+            SolveAlgorithms::Breadth,
+            SolveAlgorithms::Dijkstra,
+            SolveAlgorithms::AStar,
+        ]
+        .into_iter()
+        .find(|algorithm| {
+            name_key(&algorithm.to_string()) == name || name_key(&algorithm.get_name()) == name
+        })
+    }
 }
 
-impl ToString for GenAlgorithms {
-    fn to_string(&self) -> String {
+// Only the letters of a name matter when looking it up, so ratios are skipped too.
+fn name_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn ratio_args(name: &str, ratio: f64) -> Vec<String> {
+    Vec::from([name.to_string(), ratio.to_string()])
+}
+
+impl fmt::Display for GenAlgorithms {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            GenAlgorithms::Kruskal => "Kruskal".to_string(),
-            GenAlgorithms::Prim => "Prim".to_string(),
-            GenAlgorithms::Back => "Back".to_string(),
-            GenAlgorithms::AldousBroder => "Aldous-Broder".to_string(),
-            GenAlgorithms::GrowingTree(method) => "Growing-Tree ".to_string() + &method.to_string(),
-            GenAlgorithms::HuntAndKill => "Hunt-and-Kill".to_string(),
-            GenAlgorithms::Wilson => "Wilson".to_string(),
-            GenAlgorithms::Eller => "Eller".to_string(),
-            GenAlgorithms::Divide => "Divide".to_string(),
-            GenAlgorithms::Sidewinder => "Sidewinder".to_string(),
-            GenAlgorithms::BinaryTree(bias) => "Binary-Tree ".to_string() + &bias.to_string(),
+            GenAlgorithms::Kruskal => write!(f, "Kruskal"),
+            GenAlgorithms::Prim => write!(f, "Prim"),
+            GenAlgorithms::Back => write!(f, "Back"),
+            GenAlgorithms::AldousBroder => write!(f, "Aldous-Broder"),
+            GenAlgorithms::GrowingTree(method) => write!(f, "Growing-Tree {method}"),
+            GenAlgorithms::HuntAndKill => write!(f, "Hunt-and-Kill"),
+            GenAlgorithms::Wilson => write!(f, "Wilson"),
+            GenAlgorithms::Eller => write!(f, "Eller"),
+            GenAlgorithms::Divide => write!(f, "Divide"),
+            GenAlgorithms::Sidewinder => write!(f, "Sidewinder"),
+            GenAlgorithms::BinaryTree(bias) => write!(f, "Binary-Tree {bias}"),
         }
     }
 }
 
-impl ToString for TreeSubAlgorithm {
-    fn to_string(&self) -> String {
+impl fmt::Display for TreeSubAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            TreeSubAlgorithm::Newest => "Newest".to_string(),
-            TreeSubAlgorithm::Middle => "Middle".to_string(),
-            TreeSubAlgorithm::Oldest => "Oldest".to_string(),
-            TreeSubAlgorithm::Random => "Random".to_string(),
-            TreeSubAlgorithm::NewestMiddle(ratio) => format!("Newest-Middle {ratio}").to_string(),
-            TreeSubAlgorithm::NewestOldest(ratio) => format!("Newest-Oldest {ratio}").to_string(),
-            TreeSubAlgorithm::NewestRandom(ratio) => format!("Newest-Random {ratio}").to_string(),
-            TreeSubAlgorithm::MiddleOldest(ratio) => format!("Middle-Oldest {ratio}").to_string(),
-            TreeSubAlgorithm::MiddleRandom(ratio) => format!("Middle-Random {ratio}").to_string(),
-            TreeSubAlgorithm::OldestRandom(ratio) => {
-                format!("Oldest-Random {ratio:0.2}").to_string()
-            }
+            TreeSubAlgorithm::Newest => write!(f, "Newest"),
+            TreeSubAlgorithm::Middle => write!(f, "Middle"),
+            TreeSubAlgorithm::Oldest => write!(f, "Oldest"),
+            TreeSubAlgorithm::Random => write!(f, "Random"),
+            TreeSubAlgorithm::NewestMiddle(ratio) => write!(f, "Newest-Middle {ratio}"),
+            TreeSubAlgorithm::NewestOldest(ratio) => write!(f, "Newest-Oldest {ratio}"),
+            TreeSubAlgorithm::NewestRandom(ratio) => write!(f, "Newest-Random {ratio}"),
+            TreeSubAlgorithm::MiddleOldest(ratio) => write!(f, "Middle-Oldest {ratio}"),
+            TreeSubAlgorithm::MiddleRandom(ratio) => write!(f, "Middle-Random {ratio}"),
+            TreeSubAlgorithm::OldestRandom(ratio) => write!(f, "Oldest-Random {ratio:0.2}"),
         }
     }
 }
 
-impl ToString for BiasMethods {
-    fn to_string(&self) -> String {
+impl fmt::Display for BiasMethods {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            BiasMethods::NorthWest => "NorthWest".to_string(),
-            BiasMethods::NorthEast => "NorthEast".to_string(),
-            BiasMethods::SouthWest => "SouthWest".to_string(),
-            BiasMethods::SouthEast => "SouthEast".to_string(),
+            BiasMethods::NorthWest => write!(f, "NorthWest"),
+            BiasMethods::NorthEast => write!(f, "NorthEast"),
+            BiasMethods::SouthWest => write!(f, "SouthWest"),
+            BiasMethods::SouthEast => write!(f, "SouthEast"),
         }
     }
 }
 
-impl ToString for SolveAlgorithms {
-    fn to_string(&self) -> String {
+impl fmt::Display for SolveAlgorithms {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            SolveAlgorithms::Depth => "Depth".to_string(),
-            SolveAlgorithms::Breadth => "Breadth".to_string(),
-            SolveAlgorithms::Dijkstra => "Dijkstra".to_string(),
-            SolveAlgorithms::AStar => "A-Star".to_string(),
+            SolveAlgorithms::Depth => write!(f, "Depth"),
+            SolveAlgorithms::Breadth => write!(f, "Breadth"),
+            SolveAlgorithms::Dijkstra => write!(f, "Dijkstra"),
+            SolveAlgorithms::AStar => write!(f, "A-Star"),
         }
     }
 }
@@ -346,13 +701,12 @@ impl ToString for SolveAlgorithms {
 #[cfg(test)]
 mod app_tests {
     use super::*;
+    use crate::maze::Marker;
 
     #[test]
     fn set_height_test() {
         let mut app = App::new();
 
-        app.set_max_size(10);
-
         assert_eq!(
             app.get_height(),
             2,
@@ -369,12 +723,12 @@ mod app_tests {
             app.get_height()
         );
 
-        app.set_height(11);
+        app.set_height(MAX_SIZE + 1);
 
         assert_eq!(
             app.get_height(),
-            10,
-            "App.height should not go above 10. Got {} instead",
+            MAX_SIZE,
+            "App.height should not go above {MAX_SIZE}. Got {} instead",
             app.get_height()
         );
     }
@@ -383,8 +737,6 @@ mod app_tests {
     fn set_width_test() {
         let mut app = App::new();
 
-        app.set_max_size(10);
-
         assert_eq!(
             app.get_width(),
             2,
@@ -401,21 +753,36 @@ mod app_tests {
             app.get_width()
         );
 
-        app.set_width(11);
+        app.set_width(MAX_SIZE + 1);
 
         assert_eq!(
             app.get_width(),
-            10,
-            "App.width should not go above 10. Got {} instead",
+            MAX_SIZE,
+            "App.width should not go above {MAX_SIZE}. Got {} instead",
             app.get_width()
         );
     }
 
+    #[test]
+    fn seed_name_test() {
+        let mut app = App::new();
+
+        assert_eq!(app.get_seed_name(), "random");
+
+        app.last_seed = Some(7);
+        assert_eq!(app.get_seed_name(), "7 (random)");
+
+        app.seed = Some(42);
+        assert_eq!(app.get_seed_name(), "42");
+
+        app.clear_maze();
+        assert_eq!(app.last_seed, None, "A cleared maze has no seed");
+    }
+
     #[test]
     fn clear_maze_test() {
         let mut app = App::new();
 
-        app.set_max_size(3);
         app.set_width(3);
         app.set_height(3);
 
@@ -430,68 +797,100 @@ mod app_tests {
 # # # #
 #######";
 
-        assert_eq!(expected, app.maze);
+        assert_eq!(expected, app.maze.to_string());
+    }
+
+    #[test]
+    fn algorithm_args_test() {
+        assert_eq!(Vec::from(["Kruskal"]), GenAlgorithms::Kruskal.to_args());
+        assert_eq!(
+            Vec::from(["Growing-Tree", "Newest-Middle", "0.25"]),
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::NewestMiddle(0.25)).to_args()
+        );
+        assert_eq!(
+            Vec::from(["Binary-Tree", "SouthEast"]),
+            GenAlgorithms::BinaryTree(BiasMethods::SouthEast).to_args()
+        );
+        assert_eq!(Vec::from(["A-Star"]), SolveAlgorithms::AStar.to_args());
+    }
+
+    // A tiny maze told apart by the marker in its first cell.
+    fn step(marker: Marker) -> Maze {
+        let mut maze = Maze::closed(2, 2);
+        maze.set_marker(0, marker);
+        maze
     }
 
     #[test]
-    fn load_steps_test() {
+    fn run_follows_steps_test() {
         let mut app = App::new();
 
-        let test_str = "\
-#####
-# # #
-#####
-# # #
-#####
-
-#####
-# # #
-#####
-# # #
-#####
-
-#####
-# # #
-#####
-# # #
-#####";
-
-        let expected = vec![
-            "\
-#####
-# # #
-#####
-# # #
-#####",
-            "\
-#####
-# # #
-#####
-# # #
-#####",
-            "\
-#####
-# # #
-#####
-# # #
-#####",
-        ];
-
-        let _ = fs::write("tmp.steps", test_str);
-
-        app.load_steps("tmp.steps");
-
-        let _ = fs::remove_file("tmp.steps");
+        app.maze = step(Marker::Empty);
+        app.maze_steps.push(step(Marker::Empty));
+        app.has_generated = true;
+
+        app.begin_run();
+        app.push_step(step(Marker::Queued));
+        app.push_step(step(Marker::Observed));
 
         assert_eq!(
-            expected, app.maze_steps,
-            "Maze steps did not parse the maze correctly"
+            app.maze,
+            step(Marker::Observed),
+            "The view should follow the newest step"
         );
+        assert_eq!(app.get_step_val(), 1);
+
+        app.stop_following();
+        assert!(app.step_back());
+        app.push_step(step(Marker::Path));
+
         assert_eq!(
-            3,
-            app.maze_steps.len(),
-            "Maze steps was not 3. Got {}",
-            app.maze_steps.len()
+            app.maze,
+            step(Marker::Queued),
+            "The view should stay put after stepping"
         );
+
+        app.finish_run(step(Marker::Path));
+        assert_eq!(app.maze_steps.len(), 3);
+        assert_eq!(app.maze_steps.get(2).unwrap(), step(Marker::Path));
+    }
+
+    #[test]
+    fn abandon_run_test() {
+        let mut app = App::new();
+
+        app.maze = step(Marker::Empty);
+        app.maze_steps.push(step(Marker::Empty));
+
+        app.begin_run();
+        app.push_step(step(Marker::Queued));
+        app.abandon_run();
+
+        assert_eq!(app.maze, step(Marker::Empty));
+        assert_eq!(app.maze_steps.len(), 1);
+        assert_eq!(app.maze_steps.get(0).unwrap(), step(Marker::Empty));
+    }
+
+    #[test]
+    fn from_name_test() {
+        assert!(GenAlgorithms::from_name("kruskal", 0.5) == Some(GenAlgorithms::Kruskal));
+        assert!(
+            GenAlgorithms::from_name("Recursive Backtracking", 0.5) == Some(GenAlgorithms::Back)
+        );
+        assert!(
+            GenAlgorithms::from_name("Growing-Tree Newest-Middle", 0.25)
+                == Some(GenAlgorithms::GrowingTree(TreeSubAlgorithm::NewestMiddle(
+                    0.25
+                )))
+        );
+        assert!(
+            GenAlgorithms::from_name("binary tree southeast", 0.5)
+                == Some(GenAlgorithms::BinaryTree(BiasMethods::SouthEast))
+        );
+        assert!(GenAlgorithms::from_name("Growing-Tree", 0.5).is_none());
+
+        assert!(SolveAlgorithms::from_name("A-Star") == Some(SolveAlgorithms::AStar));
+        assert!(SolveAlgorithms::from_name("breadth first") == Some(SolveAlgorithms::Breadth));
+        assert!(SolveAlgorithms::from_name("Greedy").is_none());
     }
 }
@@ -0,0 +1,736 @@
+use std::{
+    fmt, fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    string::FromUtf8Error,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{
+    app::{GenAlgorithms, SolveAlgorithms},
+    generator,
+    maze::{Maze, ParseError},
+    solver,
+    steps::StepParser,
+    workdir::WorkDir,
+};
+
+/// How many steps can wait to be taken in before the run producing them blocks, so a run
+/// that is faster than the viewer doesn't pile up whole mazes in memory.
+pub const STEP_BACKLOG: usize = 16;
+
+/// Why a generator or solver run didn't produce a maze.
+#[derive(Debug)]
+pub enum BackendError {
+    /// The run was cancelled before it finished.
+    Cancelled,
+    /// The tool could not be started at all.
+    Spawn {
+        program: String,
+        source: io::Error,
+    },
+    /// The tool exited unsuccessfully. `stderr` holds whatever it printed.
+    Exit {
+        program: String,
+        status: ExitStatus,
+        stderr: String,
+    },
+    /// The tool printed something that isn't UTF-8.
+    InvalidUtf8 {
+        program: String,
+        source: FromUtf8Error,
+    },
+    /// A step (counting from 1) isn't a maze grid.
+    MalformedStep {
+        step: usize,
+        source: ParseError,
+    },
+    /// The final maze isn't a maze grid.
+    MalformedMaze {
+        source: ParseError,
+    },
+    Io(io::Error),
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackendError::Cancelled => write!(f, "The run was cancelled"),
+            BackendError::Spawn { program, source } => {
+                write!(f, "Failed to start {program}: {source}")
+            }
+            BackendError::Exit {
+                program,
+                status,
+                stderr,
+            } => {
+                write!(f, "{program} failed ({status})")?;
+
+                if !stderr.trim().is_empty() {
+                    write!(f, ":\n{}", stderr.trim_end())?;
+                }
+
+                Ok(())
+            }
+            BackendError::InvalidUtf8 { program, source } => {
+                write!(f, "{program} printed invalid UTF-8: {source}")
+            }
+            BackendError::MalformedStep { step, source } => {
+                write!(f, "Step {step} is not a valid maze, {source}")
+            }
+            BackendError::MalformedMaze { source } => {
+                write!(f, "The generated maze is not valid, {source}")
+            }
+            BackendError::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for BackendError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BackendError::Spawn { source, .. } => Some(source),
+            BackendError::InvalidUtf8 { source, .. } => Some(source),
+            BackendError::MalformedStep { source, .. } => Some(source),
+            BackendError::MalformedMaze { source } => Some(source),
+            BackendError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BackendError {
+    fn from(err: io::Error) -> BackendError {
+        BackendError::Io(err)
+    }
+}
+
+/// Shared flag used to abort a running backend.
+#[derive(Clone, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Receives the steps of a run as the backend produces them.
+pub struct StepSink<'a> {
+    on_step: &'a mut dyn FnMut(Maze),
+    cancel: Cancel,
+}
+
+impl<'a> StepSink<'a> {
+    pub fn new(cancel: Cancel, on_step: &'a mut dyn FnMut(Maze)) -> StepSink<'a> {
+        StepSink { on_step, cancel }
+    }
+
+    /// Hands a step to the viewer. Returns `false` once the run was cancelled.
+    pub fn push(&mut self, step: Maze) -> bool {
+        if self.cancel.is_cancelled() {
+            return false;
+        }
+
+        (self.on_step)(step);
+        true
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+}
+
+/// Something that can generate and solve mazes for the viewer.
+///
+/// Steps are pushed to `steps` as soon as they exist. Both methods return the final
+/// maze, which should match the last step. Runs happen off the UI thread and should
+/// return [`BackendError::Cancelled`] soon after the sink reports a cancel.
+pub trait MazeBackend: Send + Sync {
+    fn name(&self) -> String;
+
+    /// The generator and solver binaries this backend calls. Empty for the built-in
+    /// halves.
+    fn binaries(&self) -> (String, String) {
+        (String::new(), String::new())
+    }
+
+    /// Whether `generate` makes the same maze every time it is given the same seed.
+    fn uses_seed(&self) -> bool {
+        true
+    }
+
+    fn generate(
+        &self,
+        algorithm: &GenAlgorithms,
+        width: usize,
+        height: usize,
+        seed: u64,
+        steps: &mut StepSink,
+    ) -> Result<Maze, BackendError>;
+
+    fn solve(
+        &self,
+        maze: &Maze,
+        algorithm: &SolveAlgorithms,
+        steps: &mut StepSink,
+    ) -> Result<Maze, BackendError>;
+}
+
+/// Runs the generators and solvers compiled into the viewer.
+pub struct BuiltinBackend;
+
+impl MazeBackend for BuiltinBackend {
+    fn name(&self) -> String {
+        "Built-in".to_string()
+    }
+
+    fn generate(
+        &self,
+        algorithm: &GenAlgorithms,
+        width: usize,
+        height: usize,
+        seed: u64,
+        steps: &mut StepSink,
+    ) -> Result<Maze, BackendError> {
+        let mut last = Maze::default();
+
+        let finished = generator::generate(algorithm, width, height, seed, &mut |step| {
+            last.clone_from(&step);
+            steps.push(step)
+        });
+
+        if finished {
+            Ok(last)
+        } else {
+            Err(BackendError::Cancelled)
+        }
+    }
+
+    fn solve(
+        &self,
+        maze: &Maze,
+        algorithm: &SolveAlgorithms,
+        steps: &mut StepSink,
+    ) -> Result<Maze, BackendError> {
+        let mut last = Maze::default();
+
+        let finished = solver::solve(maze, algorithm, &mut |step| {
+            last.clone_from(&step);
+            steps.push(step)
+        });
+
+        if finished {
+            Ok(last)
+        } else {
+            Err(BackendError::Cancelled)
+        }
+    }
+}
+
+/// Calls binaries that speak the MazeCreator / MazeSolver command line.
+///
+/// An empty path falls back to the built-in implementation for that half. The seed is
+/// not forwarded since neither tool takes one. The files passed to the tools live in
+/// `files`.
+pub struct ProcessBackend {
+    pub gen_bin: String,
+    pub solve_bin: String,
+    pub files: WorkDir,
+}
+
+impl MazeBackend for ProcessBackend {
+    fn name(&self) -> String {
+        "MazeCreator/MazeSolver".to_string()
+    }
+
+    fn binaries(&self) -> (String, String) {
+        (self.gen_bin.clone(), self.solve_bin.clone())
+    }
+
+    fn uses_seed(&self) -> bool {
+        self.gen_bin.is_empty()
+    }
+
+    fn generate(
+        &self,
+        algorithm: &GenAlgorithms,
+        width: usize,
+        height: usize,
+        seed: u64,
+        steps: &mut StepSink,
+    ) -> Result<Maze, BackendError> {
+        if self.gen_bin.is_empty() {
+            return BuiltinBackend.generate(algorithm, width, height, seed, steps);
+        }
+
+        let step_path = self.files.steps_path();
+
+        let mut command = Command::new(&self.gen_bin);
+        command
+            .args(["-q", "-v"])
+            .arg(&step_path)
+            .arg("-a")
+            .args(algorithm.to_args())
+            .arg(width.to_string())
+            .arg(height.to_string());
+
+        run_command(&mut command, &step_path, self.files.keep_steps(), steps)
+    }
+
+    fn solve(
+        &self,
+        maze: &Maze,
+        algorithm: &SolveAlgorithms,
+        steps: &mut StepSink,
+    ) -> Result<Maze, BackendError> {
+        if self.solve_bin.is_empty() {
+            return BuiltinBackend.solve(maze, algorithm, steps);
+        }
+
+        let step_path = self.files.steps_path();
+        let maze_path = self.files.maze_path();
+
+        fs::write(&maze_path, maze.to_string())?;
+
+        let mut command = Command::new(&self.solve_bin);
+        command
+            .args(["-q", "-v"])
+            .arg(&step_path)
+            .arg("-i")
+            .arg(&maze_path)
+            .arg("-a")
+            .args(algorithm.to_args())
+            .arg(maze.width().to_string())
+            .arg(maze.height().to_string());
+
+        run_command(&mut command, &step_path, self.files.keep_steps(), steps)
+    }
+}
+
+// Runs the command while streaming the steps it writes to `step_path` into `steps`,
+// killing the child when the run is cancelled. Returns the maze the child printed to
+// stdout.
+fn run_command(
+    command: &mut Command,
+    step_path: &Path,
+    keep_steps: bool,
+    steps: &mut StepSink,
+) -> Result<Maze, BackendError> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let stream = StepStream::create(step_path, keep_steps)?;
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|source| {
+            let _ = fs::remove_file(step_path);
+            BackendError::Spawn {
+                program: program.clone(),
+                source,
+            }
+        })?;
+
+    // drain both pipes on the side so a chatty child can't fill them and stall
+    let stdout = drain(child.stdout.take().unwrap());
+    let stderr = drain(child.stderr.take().unwrap());
+
+    let (sender, receiver) = mpsc::sync_channel(STEP_BACKLOG);
+    let stream = stream.follow(sender);
+    let mut status = None;
+    let mut count = 0;
+
+    loop {
+        match receiver.recv_timeout(Duration::from_millis(10)) {
+            Ok(step) => {
+                count += 1;
+
+                match Maze::parse(&step) {
+                    Ok(step) => {
+                        steps.push(step);
+                    }
+                    Err(source) => {
+                        let _ = child.kill();
+                        let _ = child.wait();
+                        stream.close();
+                        return Err(BackendError::MalformedStep {
+                            step: count,
+                            source,
+                        });
+                    }
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) if status.is_some() => break,
+            Err(_) => {}
+        }
+
+        if steps.is_cancelled() {
+            let _ = child.kill();
+            let _ = child.wait();
+            stream.close();
+            return Err(BackendError::Cancelled);
+        }
+
+        if status.is_none() {
+            status = child.try_wait()?;
+
+            if status.is_some() {
+                stream.close();
+            }
+        }
+    }
+
+    let status = status.unwrap();
+    let stdout = join(stdout)?;
+    let stderr = join(stderr)?;
+
+    if !status.success() {
+        return Err(BackendError::Exit {
+            program,
+            status,
+            stderr: String::from_utf8_lossy(&stderr).into_owned(),
+        });
+    }
+
+    let maze = String::from_utf8(stdout)
+        .map_err(|source| BackendError::InvalidUtf8 { program, source })?;
+
+    Maze::parse(&maze).map_err(|source| BackendError::MalformedMaze { source })
+}
+
+fn drain<R: Read + Send + 'static>(mut pipe: R) -> JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        pipe.read_to_end(&mut buf).map(|_| buf)
+    })
+}
+
+fn join(handle: JoinHandle<io::Result<Vec<u8>>>) -> io::Result<Vec<u8>> {
+    handle
+        .join()
+        .unwrap_or_else(|_| Err(io::Error::other("pipe reader panicked")))
+}
+
+/// Where an external tool writes its steps. On Unix this is a FIFO so the steps can be
+/// read while the tool runs, elsewhere the file is followed as it grows.
+///
+/// With `keep` set the steps are left at the path once the run is over.
+struct StepStream {
+    path: PathBuf,
+    keep: bool,
+}
+
+struct FollowedStream {
+    path: PathBuf,
+    keep: bool,
+    done: Arc<AtomicBool>,
+    opened: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl StepStream {
+    fn create(path: &Path, keep: bool) -> io::Result<StepStream> {
+        let _ = fs::remove_file(path);
+
+        #[cfg(unix)]
+        {
+            use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+            let c_path = CString::new(path.as_os_str().as_bytes())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+            if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        #[cfg(not(unix))]
+        fs::write(path, "")?;
+
+        Ok(StepStream {
+            path: path.to_path_buf(),
+            keep,
+        })
+    }
+
+    fn follow(self, sender: mpsc::SyncSender<String>) -> FollowedStream {
+        let done = Arc::new(AtomicBool::new(false));
+        let opened = Arc::new(AtomicBool::new(false));
+        let path = self.path.clone();
+        let reader_done = done.clone();
+        let reader_opened = opened.clone();
+
+        // a FIFO can't be kept, so a copy is written next to it and moved over it later
+        let copy_path = (cfg!(unix) && self.keep).then(|| part_path(&self.path));
+
+        let handle = thread::spawn(move || {
+            let Ok(mut file) = fs::File::open(&path) else {
+                return;
+            };
+            reader_opened.store(true, Ordering::Relaxed);
+
+            let mut copy = copy_path.and_then(|path| fs::File::create(path).ok());
+            let mut parser = StepParser::new();
+            let mut buf = vec![0; 64 * 1024];
+
+            loop {
+                match file.read(&mut buf) {
+                    // a FIFO only reports the end once the writer is gone
+                    Ok(0) if cfg!(unix) || reader_done.load(Ordering::Relaxed) => break,
+                    Ok(0) => thread::sleep(Duration::from_millis(10)),
+                    Ok(n) => {
+                        if let Some(file) = &mut copy {
+                            let _ = file.write_all(&buf[..n]);
+                        }
+
+                        for step in parser.feed(&buf[..n]) {
+                            if sender.send(step).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                    Err(_) => break,
+                }
+            }
+
+            if let Some(step) = parser.finish() {
+                let _ = sender.send(step);
+            }
+        });
+
+        FollowedStream {
+            path: self.path,
+            keep: self.keep,
+            done,
+            opened,
+            handle,
+        }
+    }
+}
+
+impl FollowedStream {
+    // Stops following once the writer is gone. A tool that never opened the FIFO leaves
+    // the reader blocked in `open`, so a throwaway writer is opened to release it. The
+    // steps still being read are left for the receiver, which the reader may be waiting on.
+    fn close(&self) {
+        self.done.store(true, Ordering::Relaxed);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            while !self.handle.is_finished() && !self.opened.load(Ordering::Relaxed) {
+                let _ = fs::OpenOptions::new()
+                    .write(true)
+                    .custom_flags(libc::O_NONBLOCK)
+                    .open(&self.path);
+                thread::sleep(Duration::from_millis(5));
+            }
+        }
+    }
+}
+
+impl Drop for FollowedStream {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = fs::remove_file(&self.path);
+        } else if cfg!(unix) {
+            let _ = fs::remove_file(&self.path);
+            let _ = fs::rename(part_path(&self.path), &self.path);
+        }
+    }
+}
+
+fn part_path(path: &Path) -> PathBuf {
+    let mut part = path.as_os_str().to_owned();
+    part.push(".part");
+    PathBuf::from(part)
+}
+
+#[cfg(test)]
+mod backend_tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn cancel_kills_child() {
+        let cancel = Cancel::default();
+        let started = std::time::Instant::now();
+
+        let watcher = cancel.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            watcher.cancel();
+        });
+
+        let mut on_step = |_| {};
+        let mut steps = StepSink::new(cancel, &mut on_step);
+        let err = run_command(
+            Command::new("sleep").arg("5"),
+            Path::new("cancel_test.steps"),
+            false,
+            &mut steps,
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, BackendError::Cancelled));
+        assert!(
+            started.elapsed() < Duration::from_secs(2),
+            "child was not killed"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn steps_stream_while_running() {
+        let mut received = Vec::new();
+        let mut on_step = |step: Maze| received.push(step.to_string());
+        let mut steps = StepSink::new(Cancel::default(), &mut on_step);
+
+        let script = "{ printf '###\\n# #\\n###\\n\\n'; printf '###\\n#S#\\n###'; } > stream_test.steps; printf '###\\n#S#\\n###'";
+        let stdout = run_command(
+            Command::new("sh").args(["-c", script]),
+            Path::new("stream_test.steps"),
+            false,
+            &mut steps,
+        )
+        .unwrap();
+
+        assert_eq!(stdout.to_string(), "###\n#S#\n###");
+        assert_eq!(received, ["###\n# #\n###", "###\n#S#\n###"]);
+        assert!(!Path::new("stream_test.steps").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn more_steps_than_the_backlog() {
+        let mut received = 0;
+        let mut on_step = |_| {
+            thread::sleep(Duration::from_millis(1));
+            received += 1;
+        };
+        let mut steps = StepSink::new(Cancel::default(), &mut on_step);
+
+        // the child is gone long before the slow sink has taken in all the steps
+        let script = "for i in $(seq 100); do printf '###\\n# #\\n###\\n\\n'; done > backlog_test.steps; printf '###\\n# #\\n###'";
+        run_command(
+            Command::new("sh").args(["-c", script]),
+            Path::new("backlog_test.steps"),
+            false,
+            &mut steps,
+        )
+        .unwrap();
+
+        assert_eq!(received, 100);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn child_that_never_writes_steps() {
+        let mut on_step = |_| {};
+        let mut steps = StepSink::new(Cancel::default(), &mut on_step);
+
+        let stdout = run_command(
+            Command::new("printf").arg("###\\n# #\\n###"),
+            Path::new("silent_test.steps"),
+            false,
+            &mut steps,
+        )
+        .unwrap();
+
+        assert_eq!(stdout, Maze::closed(1, 1));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_child_reports_stderr() {
+        let mut on_step = |_| {};
+        let mut steps = StepSink::new(Cancel::default(), &mut on_step);
+
+        let err = run_command(
+            Command::new("sh").args(["-c", "echo 'bad size' >&2; exit 3"]),
+            Path::new("exit_test.steps"),
+            false,
+            &mut steps,
+        )
+        .unwrap_err();
+
+        match err {
+            BackendError::Exit { status, stderr, .. } => {
+                assert_eq!(status.code(), Some(3));
+                assert_eq!(stderr, "bad size\n");
+            }
+            err => panic!("expected an exit error, got {err:?}"),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn missing_binary_fails_to_spawn() {
+        let mut on_step = |_| {};
+        let mut steps = StepSink::new(Cancel::default(), &mut on_step);
+
+        let err = run_command(
+            &mut Command::new("./no-such-maze-generator"),
+            Path::new("spawn_test.steps"),
+            false,
+            &mut steps,
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, BackendError::Spawn { .. }));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn garbage_steps_are_rejected() {
+        let mut on_step = |_| {};
+        let mut steps = StepSink::new(Cancel::default(), &mut on_step);
+
+        let script = "printf '###\\n# #\\n###\\n\\nnot a maze\\n\\n' > garbage_test.steps";
+        let err = run_command(
+            Command::new("sh").args(["-c", script]),
+            Path::new("garbage_test.steps"),
+            false,
+            &mut steps,
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, BackendError::MalformedStep { step: 2, .. }));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kept_steps_outlive_the_run() {
+        let mut on_step = |_| {};
+        let mut steps = StepSink::new(Cancel::default(), &mut on_step);
+
+        let script = "printf '###\\n# #\\n###' > keep_test.steps; printf '###\\n# #\\n###'";
+        run_command(
+            Command::new("sh").args(["-c", script]),
+            Path::new("keep_test.steps"),
+            true,
+            &mut steps,
+        )
+        .unwrap();
+
+        let kept = fs::read_to_string("keep_test.steps");
+        let _ = fs::remove_file("keep_test.steps");
+
+        assert_eq!(kept.unwrap(), "###\n# #\n###");
+        assert!(!Path::new("keep_test.steps.part").exists());
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use ratatui::widgets::ListState;
+
+/// A directory listing to pick a file from.
+pub struct FileBrowser {
+    pub dir: PathBuf,
+    pub entries: Vec<Entry>,
+    pub state: ListState,
+    // files without one of these extensions are hidden, unless it's empty
+    extensions: &'static [&'static str],
+}
+
+pub struct Entry {
+    pub name: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+}
+
+impl FileBrowser {
+    /// Lists `dir`, or the directory holding it when it's a file.
+    pub fn open(dir: &Path) -> io::Result<FileBrowser> {
+        FileBrowser::open_filtered(dir, &[])
+    }
+
+    /// Like [`FileBrowser::open`], but only lists directories and files ending in one of
+    /// `extensions`.
+    pub fn open_filtered(
+        dir: &Path,
+        extensions: &'static [&'static str],
+    ) -> io::Result<FileBrowser> {
+        let dir = if dir.is_file() {
+            dir.parent().unwrap_or(Path::new("."))
+        } else {
+            dir
+        };
+
+        let mut browser = FileBrowser {
+            dir: PathBuf::new(),
+            entries: Vec::new(),
+            state: ListState::default(),
+            extensions,
+        };
+
+        browser.change_dir(dir)?;
+
+        Ok(browser)
+    }
+
+    pub fn next(&mut self) {
+        let selected = self.state.selected().unwrap_or(0);
+
+        if selected + 1 < self.entries.len() {
+            self.state.select(Some(selected + 1));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        let selected = self.state.selected().unwrap_or(0);
+
+        self.state.select(Some(selected.saturating_sub(1)));
+    }
+
+    /// Enters the selected directory, or returns the selected file.
+    pub fn select(&mut self) -> io::Result<Option<PathBuf>> {
+        let Some(entry) = self.state.selected().and_then(|i| self.entries.get(i)) else {
+            return Ok(None);
+        };
+
+        if !entry.is_dir {
+            return Ok(Some(entry.path.clone()));
+        }
+
+        let path = entry.path.clone();
+        self.change_dir(&path)?;
+
+        Ok(None)
+    }
+
+    pub fn parent(&mut self) -> io::Result<()> {
+        match self.dir.parent().map(Path::to_path_buf) {
+            Some(parent) => self.change_dir(&parent),
+            None => Ok(()),
+        }
+    }
+
+    fn shows(&self, path: &Path) -> bool {
+        self.extensions.is_empty()
+            || path
+                .extension()
+                .is_some_and(|ext| self.extensions.iter().any(|wanted| ext == *wanted))
+    }
+
+    fn change_dir(&mut self, dir: &Path) -> io::Result<()> {
+        let dir = dir.canonicalize()?;
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let is_dir = path.is_dir();
+
+            if !is_dir && !self.shows(&path) {
+                continue;
+            }
+
+            entries.push(Entry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                is_dir,
+                path,
+            });
+        }
+
+        // directories first, then by name
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+
+        if let Some(parent) = dir.parent() {
+            entries.insert(
+                0,
+                Entry {
+                    name: "..".to_string(),
+                    path: parent.to_path_buf(),
+                    is_dir: true,
+                },
+            );
+        }
+
+        self.dir = dir;
+        self.entries = entries;
+        self.state.select(Some(0));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod browser_tests {
+    use super::*;
+
+    #[test]
+    fn browse_and_pick() {
+        let root = std::env::temp_dir().join(format!("browser_test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("sub").join("maze.mz"), "").unwrap();
+        fs::write(root.join("a.steps"), "").unwrap();
+
+        let mut browser = FileBrowser::open(&root).unwrap();
+        let names: Vec<&str> = browser.entries.iter().map(|e| e.name.as_str()).collect();
+
+        assert_eq!(names, ["..", "sub", "a.steps"]);
+
+        browser.next();
+        assert_eq!(
+            browser.select().unwrap(),
+            None,
+            "Entering a directory picks nothing"
+        );
+        assert!(browser.dir.ends_with("sub"));
+
+        browser.next();
+        let picked = browser.select().unwrap().unwrap();
+        assert!(picked.ends_with("sub/maze.mz"));
+
+        browser.parent().unwrap();
+        assert_eq!(browser.dir, root.canonicalize().unwrap());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn hides_other_files() {
+        let root = std::env::temp_dir().join(format!("browser_filter-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("maze.mz"), "").unwrap();
+        fs::write(root.join("notes.txt"), "").unwrap();
+        fs::write(root.join("run.steps"), "").unwrap();
+
+        let browser = FileBrowser::open_filtered(&root, &["mz", "steps"]).unwrap();
+        let names: Vec<&str> = browser.entries.iter().map(|e| e.name.as_str()).collect();
+
+        assert_eq!(names, ["..", "sub", "maze.mz", "run.steps"]);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,804 @@
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use ratatui::style::Color;
+use serde_json::{Map, Value};
+
+use crate::{
+    app::{App, GenAlgorithms, SolveAlgorithms},
+    ui::maze_ui::GlyphSet,
+};
+
+pub const USAGE: &str = "\
+Usage: MazeViewerTUI [OPTIONS] [FILE]
+
+FILE is a maze (.mz) or step (.steps) file to show at startup.
+
+Options:
+    --config PATH       Read settings from PATH instead of the default config file
+    --gen PATH          MazeCreator binary used for generating
+    --solve PATH        MazeSolver binary used for solving
+    --steps-out PATH    Where the tools write their steps
+    --mz PATH           Where the maze is written for the solver
+    --keep              Keep the temporary files after exiting
+    --open PATH         Show the maze or steps in PATH, same as FILE
+    --steps PATH        Play back the step file in PATH, same as FILE
+    --generator NAME    Starting generator, e.g. Kruskal or \"Growing-Tree Newest\"
+    --solver NAME       Starting solver, e.g. Depth or A-Star
+    --width N           Starting maze width
+    --height N          Starting maze height
+    --speed N           Playback speed in steps per second (1-100)
+    --ratio N           Growing tree ratio (0-1)
+    --seed N            Seed for the generators
+    --glyphs NAME       Characters to draw with: ascii, light, heavy, double, rounded or blocks
+    -h, --help          Show this message";
+
+/// Settings that can come from the command line, the environment or the config file.
+///
+/// Every field is optional so the layers can be stacked with [`Config::or`]; whatever
+/// is still unset keeps the built-in default from [`App::new`].
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Config {
+    pub config_file: Option<PathBuf>,
+    pub gen_bin: Option<String>,
+    pub solve_bin: Option<String>,
+    pub steps_path: Option<PathBuf>,
+    pub maze_path: Option<PathBuf>,
+    pub keep_files: Option<bool>,
+    pub generator: Option<String>,
+    pub solver: Option<String>,
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    pub speed: Option<usize>,
+    pub ratio: Option<f64>,
+    pub seed: Option<u64>,
+    pub glyphs: Option<GlyphSet>,
+    pub colors: Colors,
+    /// A maze or step file to show at startup. Only set from the command line.
+    pub open: Option<PathBuf>,
+}
+
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Colors {
+    pub text: Option<Color>,
+    pub highlight_fg: Option<Color>,
+    pub highlight_bg: Option<Color>,
+    pub observed: Option<Color>,
+    pub queued: Option<Color>,
+    pub path: Option<Color>,
+    pub route: Option<Color>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    Write {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// `key` names the setting the way it was written, e.g. `defaults.speed` or `--speed`.
+    Invalid {
+        key: String,
+        reason: String,
+    },
+    Usage(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read { path, source } => {
+                write!(f, "Failed to read {}: {source}", path.display())
+            }
+            ConfigError::Parse { path, source } => {
+                write!(f, "Failed to parse {}: {source}", path.display())
+            }
+            ConfigError::Write { path, source } => {
+                write!(f, "Failed to write {}: {source}", path.display())
+            }
+            ConfigError::Invalid { key, reason } => write!(f, "Invalid {key}: {reason}"),
+            ConfigError::Usage(reason) => write!(f, "{reason}\n\n{USAGE}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Reads every layer. The command line wins over the environment, which wins over
+    /// the config file.
+    ///
+    /// `config_file` is left pointing at the file that was read, or at the default path
+    /// when there is none yet.
+    pub fn load(args: impl IntoIterator<Item = String>) -> Result<Config, ConfigError> {
+        let cli = Config::from_args(args)?;
+        let env = Config::from_env(|key| env::var(key).ok());
+
+        let file = match cli.config_file.as_ref().or(env.config_file.as_ref()) {
+            Some(path) => Config::from_file(path)?,
+            None => match default_path() {
+                Some(path) if path.exists() => Config {
+                    config_file: Some(path.clone()),
+                    ..Config::from_file(&path)?
+                },
+                path => Config {
+                    config_file: path,
+                    ..Config::default()
+                },
+            },
+        };
+
+        Ok(cli.or(env).or(file))
+    }
+
+    /// Stores the backend paths in the config file at `path`, keeping everything else
+    /// in it. An empty path is removed so the built-in backend is used.
+    pub fn save_backend(path: &Path, gen_bin: &str, solve_bin: &str) -> Result<(), ConfigError> {
+        let mut value = if path.exists() {
+            let text = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+                path: path.to_path_buf(),
+                source,
+            })?;
+
+            serde_json::from_str(&text).map_err(|source| ConfigError::Parse {
+                path: path.to_path_buf(),
+                source,
+            })?
+        } else {
+            Value::Object(Map::new())
+        };
+
+        let backend = value
+            .as_object_mut()
+            .ok_or_else(|| invalid("config", "expected an object"))?
+            .entry("backend")
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or_else(|| invalid("backend", "expected an object"))?;
+
+        for (key, bin) in [("generator", gen_bin), ("solver", solve_bin)] {
+            if bin.is_empty() {
+                backend.remove(key);
+            } else {
+                backend.insert(key.to_string(), Value::String(bin.to_string()));
+            }
+        }
+
+        let write_error = |source| ConfigError::Write {
+            path: path.to_path_buf(),
+            source,
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(write_error)?;
+        }
+
+        let text = serde_json::to_string_pretty(&value).unwrap();
+
+        fs::write(path, text + "\n").map_err(write_error)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Config, ConfigError> {
+        let text = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        let value: Value = serde_json::from_str(&text).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        Config::from_json(&value)
+    }
+
+    /// Reads the config file layout:
+    ///
+    /// ```json
+    /// {
+    ///     "backend": { "generator": "...", "solver": "...", "steps_file": "...",
+    ///                  "maze_file": "...", "keep_files": false },
+    ///     "defaults": { "generator": "Kruskal", "solver": "Depth", "width": 10,
+    ///                   "height": 10, "speed": 50, "ratio": 0.5, "seed": 42 },
+    ///     "colors": { "text": "white", "highlight_fg": "black", "highlight_bg": "yellow",
+    ///                 "observed": "light-red", "queued": "red", "path": "light-blue",
+    ///                 "route": "#FFD580" },
+    ///     "view": { "glyphs": "rounded" }
+    /// }
+    /// ```
+    pub fn from_json(value: &Value) -> Result<Config, ConfigError> {
+        let root = section(value, "", &["backend", "defaults", "colors", "view"])?;
+        let backend = section_of(
+            root,
+            "backend",
+            &[
+                "generator",
+                "solver",
+                "steps_file",
+                "maze_file",
+                "keep_files",
+            ],
+        )?;
+        let defaults = section_of(
+            root,
+            "defaults",
+            &[
+                "generator",
+                "solver",
+                "width",
+                "height",
+                "speed",
+                "ratio",
+                "seed",
+            ],
+        )?;
+        let colors = section_of(
+            root,
+            "colors",
+            &[
+                "text",
+                "highlight_fg",
+                "highlight_bg",
+                "observed",
+                "queued",
+                "path",
+                "route",
+            ],
+        )?;
+
+        let view = section_of(root, "view", &["glyphs"])?;
+
+        Config {
+            config_file: None,
+            gen_bin: string(&backend, "backend.generator")?,
+            solve_bin: string(&backend, "backend.solver")?,
+            steps_path: string(&backend, "backend.steps_file")?.map(PathBuf::from),
+            maze_path: string(&backend, "backend.maze_file")?.map(PathBuf::from),
+            keep_files: boolean(&backend, "backend.keep_files")?,
+            generator: string(&defaults, "defaults.generator")?,
+            solver: string(&defaults, "defaults.solver")?,
+            width: number(&defaults, "defaults.width")?,
+            height: number(&defaults, "defaults.height")?,
+            speed: number(&defaults, "defaults.speed")?,
+            ratio: ratio(&defaults, "defaults.ratio")?,
+            seed: number(&defaults, "defaults.seed")?,
+            glyphs: glyphs(&view, "view.glyphs")?,
+            colors: Colors {
+                text: color(&colors, "colors.text")?,
+                highlight_fg: color(&colors, "colors.highlight_fg")?,
+                highlight_bg: color(&colors, "colors.highlight_bg")?,
+                observed: color(&colors, "colors.observed")?,
+                queued: color(&colors, "colors.queued")?,
+                path: color(&colors, "colors.path")?,
+                route: color(&colors, "colors.route")?,
+            },
+            open: None,
+        }
+        .checked()
+    }
+
+    /// Reads `MAZE_CONFIG`, `MAZE_GEN`, `MAZE_SOLVE`, `MAZE_STEPS`, `MAZE_MZ` and
+    /// `MAZE_KEEP`. Empty variables count as unset.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Config {
+        let var = |key| var(key).filter(|value| !value.is_empty());
+
+        Config {
+            config_file: var("MAZE_CONFIG").map(PathBuf::from),
+            gen_bin: var("MAZE_GEN"),
+            solve_bin: var("MAZE_SOLVE"),
+            steps_path: var("MAZE_STEPS").map(PathBuf::from),
+            maze_path: var("MAZE_MZ").map(PathBuf::from),
+            keep_files: var("MAZE_KEEP").map(|keep| keep != "0"),
+            ..Config::default()
+        }
+    }
+
+    /// Reads the command line, without the program name.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Config, ConfigError> {
+        let mut config = Config::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if arg == "--keep" {
+                config.keep_files = Some(true);
+                continue;
+            }
+
+            if !arg.starts_with('-') {
+                if config.open.is_some() {
+                    return Err(ConfigError::Usage(format!("Unexpected argument {arg}")));
+                }
+
+                config.open = Some(PathBuf::from(arg));
+                continue;
+            }
+
+            let Some(value) = args.next() else {
+                return Err(ConfigError::Usage(format!("Missing a value for {arg}")));
+            };
+
+            match arg.as_str() {
+                "--config" => config.config_file = Some(PathBuf::from(value)),
+                "--gen" => config.gen_bin = Some(value),
+                "--solve" => config.solve_bin = Some(value),
+                "--steps-out" => config.steps_path = Some(PathBuf::from(value)),
+                "--mz" => config.maze_path = Some(PathBuf::from(value)),
+                "--open" | "--steps" => config.open = Some(PathBuf::from(value)),
+                "--generator" => config.generator = Some(value),
+                "--solver" => config.solver = Some(value),
+                "--width" => config.width = Some(parse(&arg, &value)?),
+                "--height" => config.height = Some(parse(&arg, &value)?),
+                "--speed" => config.speed = Some(parse(&arg, &value)?),
+                "--ratio" => config.ratio = Some(parse(&arg, &value)?),
+                "--seed" => config.seed = Some(parse(&arg, &value)?),
+                "--glyphs" => config.glyphs = Some(parse(&arg, &value)?),
+                _ => return Err(ConfigError::Usage(format!("Unknown option {arg}"))),
+            }
+        }
+
+        config.checked()
+    }
+
+    /// Fills whatever is unset here from `lower`.
+    pub fn or(self, lower: Config) -> Config {
+        Config {
+            config_file: self.config_file.or(lower.config_file),
+            gen_bin: self.gen_bin.or(lower.gen_bin),
+            solve_bin: self.solve_bin.or(lower.solve_bin),
+            steps_path: self.steps_path.or(lower.steps_path),
+            maze_path: self.maze_path.or(lower.maze_path),
+            keep_files: self.keep_files.or(lower.keep_files),
+            generator: self.generator.or(lower.generator),
+            solver: self.solver.or(lower.solver),
+            width: self.width.or(lower.width),
+            height: self.height.or(lower.height),
+            speed: self.speed.or(lower.speed),
+            ratio: self.ratio.or(lower.ratio),
+            seed: self.seed.or(lower.seed),
+            glyphs: self.glyphs.or(lower.glyphs),
+            open: self.open.or(lower.open),
+            colors: Colors {
+                text: self.colors.text.or(lower.colors.text),
+                highlight_fg: self.colors.highlight_fg.or(lower.colors.highlight_fg),
+                highlight_bg: self.colors.highlight_bg.or(lower.colors.highlight_bg),
+                observed: self.colors.observed.or(lower.colors.observed),
+                queued: self.colors.queued.or(lower.colors.queued),
+                path: self.colors.path.or(lower.colors.path),
+                route: self.colors.route.or(lower.colors.route),
+            },
+        }
+    }
+
+    // Makes sure the algorithm names can be looked up and the ratio is in range, so a
+    // typo is reported at startup.
+    fn checked(self) -> Result<Config, ConfigError> {
+        if let Some(ratio) = self.ratio {
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err(invalid("ratio", "expected a number between 0 and 1"));
+            }
+        }
+
+        if let Some(name) = &self.generator {
+            self.gen_algorithm(name)?;
+        }
+
+        if let Some(name) = &self.solver {
+            SolveAlgorithms::from_name(name).ok_or_else(|| ConfigError::Invalid {
+                key: "solver".to_string(),
+                reason: format!("unknown algorithm {name:?}"),
+            })?;
+        }
+
+        Ok(self)
+    }
+
+    fn gen_algorithm(&self, name: &str) -> Result<GenAlgorithms, ConfigError> {
+        GenAlgorithms::from_name(name, self.ratio.unwrap_or(0.5)).ok_or_else(|| {
+            ConfigError::Invalid {
+                key: "generator".to_string(),
+                reason: format!("unknown algorithm {name:?}"),
+            }
+        })
+    }
+
+    /// Applies the defaults, glyphs and colors. The size is left to the caller since it can only
+    /// be clamped once the terminal size is known.
+    pub fn apply(&self, app: &mut App) {
+        if let Some(speed) = self.speed {
+            app.set_speed(speed);
+        }
+
+        if let Some(ratio) = self.ratio {
+            app.set_ratio(ratio);
+        }
+
+        if self.seed.is_some() {
+            app.seed = self.seed;
+        }
+
+        if let Some(algorithm) = self
+            .generator
+            .as_ref()
+            .and_then(|name| GenAlgorithms::from_name(name, app.get_ratio()))
+        {
+            let index = app
+                .gen_algo_lookup
+                .iter()
+                .position(|el| std::mem::discriminant(el) == std::mem::discriminant(&algorithm))
+                .unwrap();
+
+            app.gen_algo_lookup[index] = algorithm.clone();
+            app.gen_list_state.select(Some(index));
+            app.gen_algorithm = algorithm;
+        }
+
+        if let Some(algorithm) = self.solver.as_deref().and_then(SolveAlgorithms::from_name) {
+            let index = app
+                .solve_algo_lookup
+                .iter()
+                .position(|el| *el == algorithm)
+                .unwrap();
+
+            app.solve_list_state.select(Some(index));
+            app.solve_algorithm = algorithm;
+        }
+
+        if let Some(glyphs) = self.glyphs {
+            app.maze_veiwer.glyphs = glyphs;
+        }
+
+        let colors = &self.colors;
+
+        if let Some(color) = colors.text {
+            app.default_color = color;
+            app.maze_veiwer.default_color = color;
+        }
+
+        if let Some(color) = colors.highlight_fg {
+            app.highlight_fg = color;
+        }
+
+        if let Some(color) = colors.highlight_bg {
+            app.highlight_bg = color;
+        }
+
+        if let Some(color) = colors.observed {
+            app.maze_veiwer.observed_color = color;
+        }
+
+        if let Some(color) = colors.queued {
+            app.maze_veiwer.queued_color = color;
+        }
+
+        if let Some(color) = colors.path {
+            app.maze_veiwer.path_color = color;
+        }
+
+        if let Some(color) = colors.route {
+            app.maze_veiwer.route_color = color;
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME/MazeViewerTUI/config.json`, falling back to `~/.config` and to
+/// `%APPDATA%` on Windows.
+pub fn default_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+
+    Some(base.join("MazeViewerTUI").join("config.json"))
+}
+
+fn parse<T: FromStr>(key: &str, value: &str) -> Result<T, ConfigError> {
+    value.parse().map_err(|_| ConfigError::Invalid {
+        key: key.to_string(),
+        reason: format!("{value:?} is not a valid value"),
+    })
+}
+
+fn invalid(key: &str, reason: &str) -> ConfigError {
+    ConfigError::Invalid {
+        key: key.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+// Returns the object at `value`, rejecting keys it doesn't know so typos don't go
+// unnoticed.
+fn section<'a>(
+    value: &'a Value,
+    key: &str,
+    known: &[&str],
+) -> Result<&'a Map<String, Value>, ConfigError> {
+    let name = if key.is_empty() { "config" } else { key };
+    let object = value
+        .as_object()
+        .ok_or_else(|| invalid(name, "expected an object"))?;
+
+    if let Some(unknown) = object.keys().find(|k| !known.contains(&k.as_str())) {
+        return Err(invalid(name, &format!("unknown setting {unknown:?}")));
+    }
+
+    Ok(object)
+}
+
+fn section_of(
+    root: &Map<String, Value>,
+    key: &str,
+    known: &[&str],
+) -> Result<Map<String, Value>, ConfigError> {
+    match root.get(key) {
+        Some(value) => section(value, key, known).cloned(),
+        None => Ok(Map::new()),
+    }
+}
+
+// The last part of `key` is the field inside `object`.
+fn field<'a>(object: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    object
+        .get(key.rsplit('.').next().unwrap())
+        .filter(|value| !value.is_null())
+}
+
+fn string(object: &Map<String, Value>, key: &str) -> Result<Option<String>, ConfigError> {
+    field(object, key)
+        .map(|value| {
+            value
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| invalid(key, "expected a string"))
+        })
+        .transpose()
+}
+
+fn boolean(object: &Map<String, Value>, key: &str) -> Result<Option<bool>, ConfigError> {
+    field(object, key)
+        .map(|value| {
+            value
+                .as_bool()
+                .ok_or_else(|| invalid(key, "expected true or false"))
+        })
+        .transpose()
+}
+
+fn number<T: TryFrom<u64>>(
+    object: &Map<String, Value>,
+    key: &str,
+) -> Result<Option<T>, ConfigError> {
+    field(object, key)
+        .map(|value| {
+            value
+                .as_u64()
+                .and_then(|n| T::try_from(n).ok())
+                .ok_or_else(|| invalid(key, "expected a positive whole number"))
+        })
+        .transpose()
+}
+
+fn ratio(object: &Map<String, Value>, key: &str) -> Result<Option<f64>, ConfigError> {
+    field(object, key)
+        .map(|value| {
+            value
+                .as_f64()
+                .filter(|ratio| (0.0..=1.0).contains(ratio))
+                .ok_or_else(|| invalid(key, "expected a number between 0 and 1"))
+        })
+        .transpose()
+}
+
+fn color(object: &Map<String, Value>, key: &str) -> Result<Option<Color>, ConfigError> {
+    field(object, key)
+        .map(|value| {
+            value
+                .as_str()
+                .and_then(|name| Color::from_str(name).ok())
+                .ok_or_else(|| invalid(key, "expected a color name or #RRGGBB"))
+        })
+        .transpose()
+}
+
+fn glyphs(object: &Map<String, Value>, key: &str) -> Result<Option<GlyphSet>, ConfigError> {
+    field(object, key)
+        .map(|value| {
+            value.as_str().and_then(GlyphSet::from_name).ok_or_else(|| {
+                invalid(
+                    key,
+                    "expected ascii, light, heavy, double, rounded or blocks",
+                )
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn from_json_test() {
+        let value = serde_json::json!({
+            "backend": { "generator": "/bin/MazeCreator", "keep_files": true },
+            "defaults": { "generator": "Prim", "speed": 20, "ratio": 0.25 },
+            "colors": { "route": "#FFD580", "path": "light-blue" },
+            "view": { "glyphs": "ascii" }
+        });
+
+        let config = Config::from_json(&value).unwrap();
+
+        assert_eq!(config.gen_bin.as_deref(), Some("/bin/MazeCreator"));
+        assert_eq!(config.keep_files, Some(true));
+        assert_eq!(config.generator.as_deref(), Some("Prim"));
+        assert_eq!(config.speed, Some(20));
+        assert_eq!(config.ratio, Some(0.25));
+        assert_eq!(config.colors.route, Some(Color::Rgb(0xFF, 0xD5, 0x80)));
+        assert_eq!(config.colors.path, Some(Color::LightBlue));
+        assert_eq!(config.glyphs, Some(GlyphSet::Ascii));
+        assert_eq!(config.solve_bin, None);
+    }
+
+    #[test]
+    fn bad_json_values() {
+        let typo = serde_json::json!({ "defaults": { "sped": 20 } });
+        let wrong_type = serde_json::json!({ "defaults": { "speed": "fast" } });
+        let bad_algorithm = serde_json::json!({ "defaults": { "solver": "Greedy" } });
+
+        assert!(
+            matches!(Config::from_json(&typo), Err(ConfigError::Invalid { key, .. }) if key == "defaults")
+        );
+        assert!(
+            matches!(Config::from_json(&wrong_type), Err(ConfigError::Invalid { key, .. }) if key == "defaults.speed")
+        );
+        assert!(
+            matches!(Config::from_json(&bad_algorithm), Err(ConfigError::Invalid { key, .. }) if key == "solver")
+        );
+    }
+
+    #[test]
+    fn save_backend_keeps_other_settings() {
+        let path = std::env::temp_dir().join(format!("config_test-{}.json", std::process::id()));
+
+        fs::write(
+            &path,
+            r#"{ "backend": { "solver": "old-solver" }, "defaults": { "speed": 20 } }"#,
+        )
+        .unwrap();
+
+        Config::save_backend(&path, "/bin/MazeCreator", "").unwrap();
+        let config = Config::from_file(&path);
+        let _ = fs::remove_file(&path);
+        let config = config.unwrap();
+
+        assert_eq!(config.gen_bin.as_deref(), Some("/bin/MazeCreator"));
+        assert_eq!(config.solve_bin, None, "An empty path means built-in");
+        assert_eq!(config.speed, Some(20));
+    }
+
+    #[test]
+    fn from_args_test() {
+        let config = Config::from_args(args(&[
+            "--gen",
+            "MazeCreator",
+            "--keep",
+            "--generator",
+            "Growing-Tree Newest",
+            "--width",
+            "12",
+            "--glyphs",
+            "Double",
+        ]))
+        .unwrap();
+
+        assert_eq!(config.gen_bin.as_deref(), Some("MazeCreator"));
+        assert_eq!(config.keep_files, Some(true));
+        assert_eq!(config.generator.as_deref(), Some("Growing-Tree Newest"));
+        assert_eq!(config.width, Some(12));
+        assert_eq!(config.glyphs, Some(GlyphSet::Double));
+
+        assert!(matches!(
+            Config::from_args(args(&["--width"])),
+            Err(ConfigError::Usage(_))
+        ));
+        assert!(matches!(
+            Config::from_args(args(&["--width", "wide"])),
+            Err(ConfigError::Invalid { .. })
+        ));
+        assert!(matches!(
+            Config::from_args(args(&["--bogus", "1"])),
+            Err(ConfigError::Usage(_))
+        ));
+        assert!(matches!(
+            Config::from_args(args(&["--ratio", "1.5"])),
+            Err(ConfigError::Invalid { key, .. }) if key == "ratio"
+        ));
+    }
+
+    #[test]
+    fn from_args_takes_a_file() {
+        let config = Config::from_args(args(&["--speed", "5", "archive/maze.mz"])).unwrap();
+
+        assert_eq!(config.open, Some(PathBuf::from("archive/maze.mz")));
+        assert_eq!(config.speed, Some(5));
+
+        let config = Config::from_args(args(&["--open", "a.steps"])).unwrap();
+        assert_eq!(config.open, Some(PathBuf::from("a.steps")));
+
+        let config =
+            Config::from_args(args(&["--steps", "b.steps", "--steps-out", "c.steps"])).unwrap();
+        assert_eq!(config.open, Some(PathBuf::from("b.steps")));
+        assert_eq!(config.steps_path, Some(PathBuf::from("c.steps")));
+
+        assert!(matches!(
+            Config::from_args(args(&["a.mz", "b.mz"])),
+            Err(ConfigError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn layers_take_precedence() {
+        let cli = Config {
+            speed: Some(90),
+            ..Config::default()
+        };
+        let env = Config::from_env(|key| match key {
+            "MAZE_GEN" => Some("env-gen".to_string()),
+            "MAZE_SOLVE" => Some(String::new()),
+            _ => None,
+        });
+        let file = Config {
+            gen_bin: Some("file-gen".to_string()),
+            solve_bin: Some("file-solve".to_string()),
+            speed: Some(10),
+            width: Some(8),
+            ..Config::default()
+        };
+
+        let config = cli.or(env).or(file);
+
+        assert_eq!(config.speed, Some(90));
+        assert_eq!(config.gen_bin.as_deref(), Some("env-gen"));
+        assert_eq!(config.solve_bin.as_deref(), Some("file-solve"));
+        assert_eq!(config.width, Some(8));
+        assert_eq!(config.height, None);
+    }
+
+    #[test]
+    fn apply_test() {
+        let mut app = App::new();
+        let config = Config {
+            generator: Some("Binary-Tree SouthEast".to_string()),
+            solver: Some("A-Star".to_string()),
+            speed: Some(20),
+            colors: Colors {
+                text: Some(Color::Green),
+                ..Colors::default()
+            },
+            ..Config::default()
+        };
+
+        config.apply(&mut app);
+
+        assert!(app.gen_algorithm == GenAlgorithms::BinaryTree(crate::app::BiasMethods::SouthEast));
+        assert_eq!(app.gen_list_state.selected(), Some(10));
+        assert!(app.solve_algorithm == SolveAlgorithms::AStar);
+        assert_eq!(app.solve_list_state.selected(), Some(3));
+        assert_eq!(app.get_speed(), 20);
+        assert_eq!(app.default_color, Color::Green);
+    }
+}
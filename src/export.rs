@@ -0,0 +1,78 @@
+mod cast;
+mod gif;
+mod svg;
+
+use ratatui::style::Color;
+
+pub use cast::write_cast;
+pub use gif::write_gif;
+pub use svg::write_svg;
+
+/// The RGB value of `color` in the xterm palette. `Reset` is taken as white on black.
+pub fn rgb(color: Color, background: bool) -> (u8, u8, u8) {
+    const ANSI: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+
+    match color {
+        Color::Reset if background => ANSI[0],
+        Color::Reset => ANSI[15],
+        Color::Black => ANSI[0],
+        Color::Red => ANSI[1],
+        Color::Green => ANSI[2],
+        Color::Yellow => ANSI[3],
+        Color::Blue => ANSI[4],
+        Color::Magenta => ANSI[5],
+        Color::Cyan => ANSI[6],
+        Color::Gray => ANSI[7],
+        Color::DarkGray => ANSI[8],
+        Color::LightRed => ANSI[9],
+        Color::LightGreen => ANSI[10],
+        Color::LightYellow => ANSI[11],
+        Color::LightBlue => ANSI[12],
+        Color::LightMagenta => ANSI[13],
+        Color::LightCyan => ANSI[14],
+        Color::White => ANSI[15],
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(i) if i < 16 => ANSI[usize::from(i)],
+        // the 6x6x6 color cube, then a ramp of grays
+        Color::Indexed(i) if i < 232 => {
+            let level = |n: u8| if n == 0 { 0 } else { 55 + 40 * n };
+            let i = i - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        Color::Indexed(i) => {
+            let gray = 8 + 10 * (i - 232);
+            (gray, gray, gray)
+        }
+    }
+}
+
+#[cfg(test)]
+mod export_tests {
+    use super::*;
+
+    #[test]
+    fn palette() {
+        assert_eq!(rgb(Color::Reset, false), (255, 255, 255));
+        assert_eq!(rgb(Color::Reset, true), (0, 0, 0));
+        assert_eq!(rgb(Color::Indexed(9), false), rgb(Color::LightRed, false));
+        assert_eq!(rgb(Color::Indexed(196), false), (255, 0, 0));
+        assert_eq!(rgb(Color::Indexed(255), false), (238, 238, 238));
+    }
+}
@@ -0,0 +1,168 @@
+use std::io::{self, Write};
+
+use ratatui::{buffer::Cell, style::Color};
+use serde_json::json;
+
+use crate::{
+    steps::Steps,
+    ui::maze_ui::{render_maze, MazeView, RenderMode},
+};
+
+/// Writes `steps` as an asciinema v2 recording, drawn in `mode` with the colors of `view`
+/// and played back `period` milliseconds apart.
+pub fn write_cast(
+    mut out: impl Write,
+    steps: &Steps,
+    view: &MazeView,
+    mode: RenderMode,
+    period: u64,
+) -> io::Result<()> {
+    let Some(last) = steps.last() else {
+        return Err(io::Error::other("there are no steps to export"));
+    };
+
+    let (width, height) = mode.size(&last);
+    let header = json!({
+        "version": 2,
+        "width": width,
+        "height": height,
+        "env": { "TERM": "xterm-256color" },
+    });
+    writeln!(out, "{header}")?;
+
+    let mut size = None;
+
+    for i in 0..steps.len() {
+        let rows = render_maze(view, &steps.get(i)?, mode);
+        let mut text = String::new();
+
+        // only clear when the maze changes shape, so frames don't flicker
+        if size != Some((rows[0].len(), rows.len())) {
+            text += "\x1b[2J";
+            size = Some((rows[0].len(), rows.len()));
+        }
+
+        text += "\x1b[H";
+        draw(&mut text, &rows);
+
+        let time = (i as u64 * period) as f64 / 1000.0;
+        writeln!(out, "{}", json!([time, "o", text]))?;
+    }
+
+    out.flush()
+}
+
+// Appends the cells of `rows` with SGR colors, switching colors only where they change.
+fn draw(text: &mut String, rows: &[Vec<Cell>]) {
+    let mut style = None;
+
+    for (y, row) in rows.iter().enumerate() {
+        if y > 0 {
+            *text += "\r\n";
+        }
+
+        for cell in row {
+            if style != Some((cell.fg, cell.bg)) {
+                style = Some((cell.fg, cell.bg));
+                *text += &format!("\x1b[0;{};{}m", sgr(cell.fg, false), sgr(cell.bg, true));
+            }
+
+            *text += cell.symbol();
+        }
+    }
+
+    *text += "\x1b[0m";
+}
+
+fn sgr(color: Color, background: bool) -> String {
+    let offset = if background { 10 } else { 0 };
+    let code = |code: u8| (code + offset).to_string();
+
+    match color {
+        Color::Reset => code(39),
+        Color::Black => code(30),
+        Color::Red => code(31),
+        Color::Green => code(32),
+        Color::Yellow => code(33),
+        Color::Blue => code(34),
+        Color::Magenta => code(35),
+        Color::Cyan => code(36),
+        Color::Gray => code(37),
+        Color::DarkGray => code(90),
+        Color::LightRed => code(91),
+        Color::LightGreen => code(92),
+        Color::LightYellow => code(93),
+        Color::LightBlue => code(94),
+        Color::LightMagenta => code(95),
+        Color::LightCyan => code(96),
+        Color::White => code(97),
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", 38 + offset),
+        Color::Indexed(i) => format!("{};5;{i}", 38 + offset),
+    }
+}
+
+#[cfg(test)]
+mod cast_tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::maze::Maze;
+
+    #[test]
+    fn writes_a_frame_per_step() {
+        let mut steps = Steps::default();
+        steps.push(Maze::parse("#####\n#   #\n#####").unwrap());
+        steps.push(Maze::parse("#####\n#s x#\n#####").unwrap());
+
+        let mut out = Vec::new();
+        write_cast(&mut out, &steps, &MazeView::new(), RenderMode::Box, 250).unwrap();
+
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 5);
+        assert_eq!(lines[0]["height"], 3);
+
+        assert_eq!(lines[1][0], 0.0);
+        assert_eq!(lines[2][0], 0.25);
+        assert_eq!(lines[2][1], "o");
+
+        let frame = lines[2][2].as_str().unwrap();
+        assert!(frame.starts_with("\x1b[H\x1b[0;97;49m┏━━━┓\r\n┃▶"));
+        // the route between the start and stop is drawn in the route color
+        assert!(frame.contains("\x1b[0;38;2;255;213;128;49m─"));
+    }
+
+    #[test]
+    fn draws_in_the_given_mode() {
+        let mut steps = Steps::default();
+        steps.push(Maze::parse("#####\n#s x#\n#####").unwrap());
+
+        let mut out = Vec::new();
+        write_cast(&mut out, &steps, &MazeView::new(), RenderMode::Braille, 250).unwrap();
+
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines[0]["width"], 3);
+        assert_eq!(lines[0]["height"], 1);
+
+        let frame = lines[1][2].as_str().unwrap();
+        assert!(!frame.contains("\r\n"));
+        assert_eq!(
+            frame
+                .chars()
+                .filter(|c| ('\u{2800}'..='\u{28ff}').contains(c))
+                .count(),
+            3
+        );
+    }
+}
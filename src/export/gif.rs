@@ -0,0 +1,260 @@
+use std::io::{self, Write};
+
+use gif::{Encoder, Frame, Repeat};
+use ratatui::style::Color;
+
+use crate::{
+    maze::{Direction, Marker, Maze},
+    steps::Steps,
+    ui::maze_ui::{get_links, MazeView},
+};
+
+use super::rgb;
+
+// Pixel sizes of a wall and of the inside of a cell, and the width of the path line.
+const WALL: usize = 2;
+const CELL: usize = 8;
+const LINE: usize = 2;
+
+// Longer runs skip steps to stay under this many frames.
+const MAX_FRAMES: usize = 300;
+// Viewers slow down GIF frames shorter than this, so faster playback skips steps instead.
+const MIN_DELAY: u64 = 20;
+// How long the last step stays up before the animation starts over.
+const HOLD: u16 = 200;
+
+// Palette entries.
+const BACKGROUND: u8 = 0;
+const WALLS: u8 = 1;
+const OBSERVED: u8 = 2;
+const QUEUED: u8 = 3;
+const PATH: u8 = 4;
+const ROUTE: u8 = 5;
+
+/// Writes `steps` as a looping GIF drawn with the colors of `view`, `period` milliseconds
+/// per step. Long or fast runs skip steps, keeping the same overall duration. Steps that
+/// aren't the size of the last one are left out.
+pub fn write_gif(out: impl Write, steps: &Steps, view: &MazeView, period: u64) -> io::Result<()> {
+    let Some(last) = steps.last() else {
+        return Err(io::Error::other("there are no steps to export"));
+    };
+
+    let width = pixels(2 * last.width() + 1);
+    let height = pixels(2 * last.height() + 1);
+    let too_large = || io::Error::other("the maze is too large for a GIF");
+
+    let palette: Vec<u8> = [
+        rgb(Color::Reset, true),
+        rgb(view.default_color, false),
+        rgb(view.observed_color, false),
+        rgb(view.queued_color, false),
+        rgb(view.path_color, false),
+        rgb(view.route_color, false),
+    ]
+    .into_iter()
+    .flat_map(|(r, g, b)| [r, g, b])
+    .collect();
+
+    let mut encoder = Encoder::new(
+        out,
+        u16::try_from(width).map_err(|_| too_large())?,
+        u16::try_from(height).map_err(|_| too_large())?,
+        &palette,
+    )
+    .map_err(io::Error::other)?;
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    let skip = steps
+        .len()
+        .div_ceil(MAX_FRAMES)
+        .max(MIN_DELAY.div_ceil(period.max(1)) as usize);
+    let delay = u16::try_from((period * skip as u64).div_ceil(10)).unwrap_or(u16::MAX);
+
+    // every `skip`th step, always ending on the last one
+    let picked = (0..steps.len())
+        .step_by(skip)
+        .chain(Some(steps.len() - 1).filter(|last| last % skip != 0));
+
+    for i in picked {
+        let maze = steps.get(i)?;
+
+        if maze.width() != last.width() || maze.height() != last.height() {
+            continue;
+        }
+
+        let mut frame =
+            Frame::from_indexed_pixels(width as u16, height as u16, rasterize(&maze), None);
+        frame.delay = if i == steps.len() - 1 {
+            HOLD.max(delay)
+        } else {
+            delay
+        };
+
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
+
+// The pixel size of `tiles` rows or columns of the text grid, which alternate between
+// walls and cells.
+fn pixels(tiles: usize) -> usize {
+    tiles / 2 * (WALL + CELL) + tiles % 2 * WALL
+}
+
+// Draws `maze` as palette indices, tile by tile the way the viewer lays out its text.
+fn rasterize(maze: &Maze) -> Vec<u8> {
+    let columns = 2 * maze.width() + 1;
+    let rows = 2 * maze.height() + 1;
+    let width = pixels(columns);
+    let mut image = vec![BACKGROUND; width * pixels(rows)];
+
+    let mut fill = |x: usize, y: usize, w: usize, h: usize, color: u8| {
+        for row in image[y * width..].chunks_mut(width).take(h) {
+            row[x..x + w].fill(color);
+        }
+    };
+
+    // the pixel rectangle of a tile
+    let tile = |column: usize, row: usize| {
+        let size = |n: usize| if n.is_multiple_of(2) { WALL } else { CELL };
+        (pixels(column), pixels(row), size(column), size(row))
+    };
+
+    let mut walls = vec![false; columns * rows];
+
+    for i in 0..maze.len() {
+        let (column, row) = (2 * maze.x(i) + 1, 2 * maze.y(i) + 1);
+
+        for (direction, (column, row)) in [
+            (Direction::Up, (column, row - 1)),
+            (Direction::Down, (column, row + 1)),
+            (Direction::Left, (column - 1, row)),
+            (Direction::Right, (column + 1, row)),
+        ] {
+            if maze.has_wall(i, direction) {
+                walls[row * columns + column] = true;
+            }
+        }
+    }
+
+    for row in 0..rows {
+        for column in 0..columns {
+            let wall = |column: usize, row: usize| {
+                column < columns && row < rows && walls[row * columns + column]
+            };
+
+            // a corner is drawn when a wall meets it, as in the viewer
+            let corner = row.is_multiple_of(2)
+                && column.is_multiple_of(2)
+                && (wall(column + 1, row)
+                    || wall(column.wrapping_sub(1), row)
+                    || wall(column, row + 1)
+                    || wall(column, row.wrapping_sub(1)));
+
+            if wall(column, row) || corner {
+                let (x, y, w, h) = tile(column, row);
+                fill(x, y, w, h, WALLS);
+            }
+        }
+    }
+
+    for i in 0..maze.len() {
+        let marker = maze.marker(i);
+        let (x, y, _, _) = tile(2 * maze.x(i) + 1, 2 * maze.y(i) + 1);
+        let (cx, cy) = (x + (CELL - LINE) / 2, y + (CELL - LINE) / 2);
+
+        if marker.is_start() || marker.is_stop() {
+            fill(x + 1, y + 1, CELL - 2, CELL - 2, WALLS);
+        } else if marker.is_path() {
+            fill(
+                cx,
+                cy,
+                LINE,
+                LINE,
+                if marker.is_route() { ROUTE } else { PATH },
+            );
+        } else if marker == Marker::Observed {
+            fill(x, y, CELL, CELL, OBSERVED);
+        } else if marker.is_queued() {
+            fill(x, y, CELL, CELL, QUEUED);
+        }
+
+        // lines to the next cell right and down, through the open wall between them
+        let (_, path_down, _, path_right) = get_links(maze, i, Marker::is_path);
+        let (_, route_down, _, route_right) = get_links(maze, i, Marker::is_route);
+        let span = CELL + WALL + LINE;
+
+        if path_right {
+            fill(cx, cy, span, LINE, if route_right { ROUTE } else { PATH });
+        }
+
+        if path_down {
+            fill(cx, cy, LINE, span, if route_down { ROUTE } else { PATH });
+        }
+    }
+
+    image
+}
+
+#[cfg(test)]
+mod gif_tests {
+    use super::*;
+
+    fn decode(data: &[u8]) -> (u16, u16, Vec<(u16, Vec<u8>)>) {
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(data).unwrap();
+        let (width, height) = (decoder.width(), decoder.height());
+        let mut frames = Vec::new();
+
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((frame.delay, frame.buffer.to_vec()));
+        }
+
+        (width, height, frames)
+    }
+
+    #[test]
+    fn animates_every_step() {
+        let mut steps = Steps::default();
+        steps.push(Maze::parse("#####\n#Q  #\n#####").unwrap());
+        steps.push(Maze::parse("#####\n#s x#\n#####").unwrap());
+
+        let mut out = Vec::new();
+        write_gif(&mut out, &steps, &MazeView::new(), 500).unwrap();
+
+        let (width, height, frames) = decode(&out);
+        assert_eq!((width, height), (22, 12));
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].0, 50);
+        assert_eq!(frames[1].0, HOLD);
+
+        let at = |frame: &Vec<u8>, x: usize, y: usize| frame[y * 22 + x];
+
+        // the outer wall, then the queued first cell
+        assert_eq!(at(&frames[0].1, 0, 0), WALLS);
+        assert_eq!(at(&frames[0].1, 5, 5), QUEUED);
+        // the route runs through the gap between the start and the stop
+        assert_eq!(at(&frames[1].1, 11, 5), ROUTE);
+        assert_eq!(at(&frames[1].1, 11, 3), BACKGROUND);
+    }
+
+    #[test]
+    fn skips_steps_of_long_runs() {
+        let mut steps = Steps::default();
+        for _ in 0..1000 {
+            steps.push(Maze::closed(2, 2));
+        }
+
+        let mut out = Vec::new();
+        write_gif(&mut out, &steps, &MazeView::new(), 10).unwrap();
+
+        let (_, _, frames) = decode(&out);
+        // every 4th step, and the last one
+        assert_eq!(frames.len(), 251);
+        assert_eq!(frames[0].0, 4);
+    }
+}
@@ -0,0 +1,186 @@
+use std::io::{self, Write};
+
+use ratatui::style::Color;
+
+use crate::{
+    maze::{Direction, Marker, Maze},
+    ui::maze_ui::{get_links, MazeView},
+};
+
+use super::rgb;
+
+// Size of a cell, and of the border around the maze, in SVG units.
+const CELL: usize = 16;
+const MARGIN: usize = 8;
+
+/// Draws `maze` as an SVG image with the colors of `view`. Cells are picked out the same
+/// way the viewer does: the start and stop get their letter, the path and route are
+/// lines through the cell centers, and observed and queued cells are filled.
+pub fn write_svg(mut out: impl Write, maze: &Maze, view: &MazeView) -> io::Result<()> {
+    let width = 2 * MARGIN + maze.width() * CELL;
+    let height = 2 * MARGIN + maze.height() * CELL;
+    let hex = |color| {
+        let (r, g, b) = rgb(color, false);
+        format!("#{r:02x}{g:02x}{b:02x}")
+    };
+    let (r, g, b) = rgb(Color::Reset, true);
+
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )?;
+    writeln!(
+        out,
+        r##"<rect width="100%" height="100%" fill="#{r:02x}{g:02x}{b:02x}"/>"##
+    )?;
+
+    let corner = |i| (MARGIN + maze.x(i) * CELL, MARGIN + maze.y(i) * CELL);
+    let center = |i| {
+        let (x, y) = corner(i);
+        (x + CELL / 2, y + CELL / 2)
+    };
+
+    for i in 0..maze.len() {
+        let marker = maze.marker(i);
+
+        let fill = if marker.is_start() || marker.is_stop() || marker.is_path() {
+            None
+        } else if marker == Marker::Observed {
+            Some(view.observed_color)
+        } else if marker.is_queued() {
+            Some(view.queued_color)
+        } else {
+            None
+        };
+
+        if let Some(fill) = fill {
+            let (x, y) = corner(i);
+            writeln!(
+                out,
+                r#"<rect x="{x}" y="{y}" width="{CELL}" height="{CELL}" fill="{}"/>"#,
+                hex(fill)
+            )?;
+        }
+    }
+
+    // the route is drawn over the path, as in the viewer
+    for (on_line, color) in [
+        (Marker::is_path as fn(Marker) -> bool, view.path_color),
+        (Marker::is_route, view.route_color),
+    ] {
+        let mut d = String::new();
+
+        for i in 0..maze.len() {
+            let (_, down, _, right) = get_links(maze, i, on_line);
+            let (x, y) = center(i);
+
+            for (linked, direction) in [(right, Direction::Right), (down, Direction::Down)] {
+                if let Some(next) = maze.neighbour(i, direction).filter(|_| linked) {
+                    let (nx, ny) = center(next);
+                    d += &format!("M{x} {y}L{nx} {ny}");
+                }
+            }
+        }
+
+        if !d.is_empty() {
+            writeln!(
+                out,
+                r#"<path d="{d}" stroke="{}" stroke-width="2" stroke-linecap="round" fill="none"/>"#,
+                hex(color)
+            )?;
+        }
+    }
+
+    let mut d = String::new();
+
+    for i in 0..maze.len() {
+        let (x, y) = corner(i);
+
+        if maze.has_wall(i, Direction::Up) {
+            d += &format!("M{x} {y}h{CELL}");
+        }
+
+        if maze.has_wall(i, Direction::Left) {
+            d += &format!("M{x} {y}v{CELL}");
+        }
+
+        if maze.x(i) + 1 == maze.width() && maze.has_wall(i, Direction::Right) {
+            d += &format!("M{} {y}v{CELL}", x + CELL);
+        }
+
+        if maze.y(i) + 1 == maze.height() && maze.has_wall(i, Direction::Down) {
+            d += &format!("M{x} {}h{CELL}", y + CELL);
+        }
+    }
+
+    writeln!(
+        out,
+        r#"<path d="{d}" stroke="{}" stroke-width="2" stroke-linecap="square" fill="none"/>"#,
+        hex(view.default_color)
+    )?;
+
+    for i in 0..maze.len() {
+        let marker = maze.marker(i);
+
+        if marker.is_start() || marker.is_stop() {
+            let (x, y) = center(i);
+            writeln!(
+                out,
+                r#"<text x="{x}" y="{y}" fill="{}" font-family="monospace" font-size="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                hex(view.default_color),
+                CELL * 3 / 4,
+                marker.to_char()
+            )?;
+        }
+    }
+
+    writeln!(out, "</svg>")?;
+
+    out.flush()
+}
+
+#[cfg(test)]
+mod svg_tests {
+    use super::*;
+
+    fn draw(maze: &str) -> String {
+        let mut out = Vec::new();
+        write_svg(&mut out, &Maze::parse(maze).unwrap(), &MazeView::new()).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn draws_the_route_and_markers() {
+        let svg = draw(
+            "\
+#######
+#s * *#
+##### #
+#x * *#
+#######",
+        );
+
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="64" height="48""#)
+        );
+        assert!(svg.trim_end().ends_with("</svg>"));
+
+        // from the start along the top, down and back along the bottom to the stop
+        assert!(svg.contains(
+            r##"<path d="M16 16L32 16M32 16L48 16M48 16L48 32M16 32L32 32M32 32L48 32" stroke="#ffd580""##
+        ));
+        // the first cell has a wall above and to the left
+        assert!(svg.contains("M8 8h16M8 8v16"));
+        assert!(svg.contains(">s</text>"));
+        assert!(svg.contains(">x</text>"));
+    }
+
+    #[test]
+    fn fills_queued_and_observed_cells() {
+        let svg = draw("#######\n#Q :  #\n#######");
+
+        assert!(svg.contains(r##"<rect x="8" y="8" width="16" height="16" fill="#cd0000"/>"##));
+        assert!(svg.contains(r##"<rect x="24" y="8" width="16" height="16" fill="#ff0000"/>"##));
+        assert!(!svg.contains(r#"<rect x="40""#));
+    }
+}
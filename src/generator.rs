@@ -0,0 +1,266 @@
+mod aldous_broder;
+mod backtrack;
+mod binary_tree;
+mod divide;
+mod eller;
+mod growing_tree;
+mod hunt_and_kill;
+mod kruskal;
+mod prim;
+mod sidewinder;
+mod wilson;
+
+use crate::{
+    app::GenAlgorithms,
+    maze::{Marker, Maze},
+    rng::Rng,
+};
+
+/// Builds a maze in-process and hands every step to `on_step`. The last step is the
+/// finished maze.
+///
+/// Returning `false` from `on_step` stops the recording, in which case this returns
+/// `false` as well.
+pub fn generate(
+    algorithm: &GenAlgorithms,
+    width: usize,
+    height: usize,
+    seed: u64,
+    on_step: &mut dyn FnMut(Maze) -> bool,
+) -> bool {
+    let mut rng = Rng::new(seed);
+    let maze = match algorithm {
+        GenAlgorithms::Divide => Maze::open(width, height),
+        _ => Maze::closed(width, height),
+    };
+    let mut grid = Grid {
+        maze,
+        on_step,
+        recording: true,
+    };
+
+    grid.snapshot();
+
+    match algorithm {
+        GenAlgorithms::Kruskal => kruskal::kruskal(&mut grid, &mut rng),
+        GenAlgorithms::Prim => prim::prim(&mut grid, &mut rng),
+        GenAlgorithms::Back => backtrack::backtrack(&mut grid, &mut rng),
+        GenAlgorithms::AldousBroder => aldous_broder::aldous_broder(&mut grid, &mut rng),
+        GenAlgorithms::GrowingTree(method) => {
+            growing_tree::growing_tree(&mut grid, &mut rng, method)
+        }
+        GenAlgorithms::HuntAndKill => hunt_and_kill::hunt_and_kill(&mut grid, &mut rng),
+        GenAlgorithms::Wilson => wilson::wilson(&mut grid, &mut rng),
+        GenAlgorithms::Eller => eller::eller(&mut grid, &mut rng),
+        GenAlgorithms::Divide => divide::divide(&mut grid, &mut rng),
+        GenAlgorithms::Sidewinder => sidewinder::sidewinder(&mut grid, &mut rng),
+        GenAlgorithms::BinaryTree(bias) => binary_tree::binary_tree(&mut grid, &mut rng, bias),
+    }
+
+    grid.finish()
+}
+
+/// A maze being generated that emits a copy of itself every time an algorithm calls
+/// [`Grid::snapshot`].
+///
+/// Cells are addressed by `y * width + x`.
+pub struct Grid<'a> {
+    maze: Maze,
+    on_step: &'a mut dyn FnMut(Maze) -> bool,
+    recording: bool,
+}
+
+impl Grid<'_> {
+    pub fn width(&self) -> usize {
+        self.maze.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.maze.height()
+    }
+
+    pub fn len(&self) -> usize {
+        self.maze.len()
+    }
+
+    pub fn x(&self, cell: usize) -> usize {
+        self.maze.x(cell)
+    }
+
+    pub fn y(&self, cell: usize) -> usize {
+        self.maze.y(cell)
+    }
+
+    pub fn cell(&self, x: usize, y: usize) -> usize {
+        self.maze.index(x, y)
+    }
+
+    pub fn neighbours(&self, cell: usize) -> Vec<usize> {
+        self.maze.neighbours(cell)
+    }
+
+    /// Removes the wall between two adjacent cells.
+    pub fn carve(&mut self, a: usize, b: usize) {
+        self.maze.carve(a, b);
+    }
+
+    /// Places a wall between two adjacent cells.
+    pub fn build_wall(&mut self, a: usize, b: usize) {
+        self.maze.build_wall(a, b);
+    }
+
+    pub fn mark(&mut self, cell: usize, marker: Marker) {
+        self.maze.set_marker(cell, marker);
+    }
+
+    pub fn unmark(&mut self, cell: usize) {
+        self.mark(cell, Marker::Empty);
+    }
+
+    // Once the receiver stops listening the algorithm still runs to the end, which is
+    // cheap without the copies.
+    pub fn snapshot(&mut self) {
+        if self.recording {
+            self.recording = (self.on_step)(self.maze.clone());
+        }
+    }
+
+    fn finish(mut self) -> bool {
+        self.maze.clear_markers();
+
+        self.mark(0, Marker::Start);
+        self.mark(self.len() - 1, Marker::Stop);
+        self.snapshot();
+
+        self.recording
+    }
+}
+
+#[cfg(test)]
+mod generator_tests {
+    use super::*;
+    use crate::app::{BiasMethods, TreeSubAlgorithm};
+
+    fn steps(algorithm: &GenAlgorithms, width: usize, height: usize, seed: u64) -> Vec<Maze> {
+        let mut steps = Vec::new();
+
+        generate(algorithm, width, height, seed, &mut |step| {
+            steps.push(step);
+            true
+        });
+
+        steps
+    }
+
+    fn all_algorithms() -> Vec<GenAlgorithms> {
+        Vec::from([
+            GenAlgorithms::Kruskal,
+            GenAlgorithms::Prim,
+            GenAlgorithms::Back,
+            GenAlgorithms::AldousBroder,
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::Newest),
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::Middle),
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::Oldest),
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::Random),
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::NewestMiddle(0.5)),
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::NewestOldest(0.5)),
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::NewestRandom(0.5)),
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::MiddleOldest(0.5)),
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::MiddleRandom(0.5)),
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::OldestRandom(0.5)),
+            GenAlgorithms::HuntAndKill,
+            GenAlgorithms::Wilson,
+            GenAlgorithms::Eller,
+            GenAlgorithms::Divide,
+            GenAlgorithms::Sidewinder,
+            GenAlgorithms::BinaryTree(BiasMethods::NorthWest),
+            GenAlgorithms::BinaryTree(BiasMethods::NorthEast),
+            GenAlgorithms::BinaryTree(BiasMethods::SouthWest),
+            GenAlgorithms::BinaryTree(BiasMethods::SouthEast),
+        ])
+    }
+
+    // A perfect maze on w * h cells has exactly w * h - 1 passages and every cell is
+    // reachable from the start.
+    fn assert_perfect(maze: &Maze, width: usize, height: usize, name: &str) {
+        assert_eq!(maze.width(), width, "{name}: wrong width");
+        assert_eq!(maze.height(), height, "{name}: wrong height");
+        assert_eq!(maze.marker(0), Marker::Start, "{name}: start missing");
+        assert_eq!(
+            maze.marker(maze.len() - 1),
+            Marker::Stop,
+            "{name}: stop missing"
+        );
+
+        let mut seen = vec![false; maze.len()];
+        let mut stack = vec![0];
+        seen[0] = true;
+
+        while let Some(cell) = stack.pop() {
+            for next in maze.open_neighbours(cell) {
+                if !seen[next] {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        // every passage is seen from both of its cells
+        let passages = (0..maze.len())
+            .map(|cell| maze.open_neighbours(cell).len())
+            .sum::<usize>()
+            / 2;
+
+        assert!(seen.iter().all(|&s| s), "{name}: unreachable cells");
+        assert_eq!(passages, width * height - 1, "{name}: maze has loops");
+    }
+
+    #[test]
+    fn every_algorithm_makes_a_perfect_maze() {
+        for algorithm in all_algorithms() {
+            let steps = steps(&algorithm, 7, 5, 1234);
+            let name = algorithm.get_name();
+
+            assert!(steps.len() > 1, "{name}: expected intermediate steps");
+            assert_perfect(steps.last().unwrap(), 7, 5, &name);
+        }
+    }
+
+    #[test]
+    fn steps_keep_the_grid_shape() {
+        for algorithm in all_algorithms() {
+            for step in steps(&algorithm, 4, 6, 99) {
+                let text = step.to_string();
+                let rows: Vec<&str> = text.split('\n').collect();
+
+                assert_eq!(rows.len(), 13);
+                assert!(rows.iter().all(|r| r.len() == 9));
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_maze() {
+        for algorithm in all_algorithms() {
+            assert_eq!(
+                steps(&algorithm, 6, 6, 5),
+                steps(&algorithm, 6, 6, 5),
+                "{} was not reproducible",
+                algorithm.get_name()
+            );
+        }
+    }
+
+    #[test]
+    fn stops_when_the_receiver_does() {
+        let mut count = 0;
+
+        let finished = generate(&GenAlgorithms::Back, 10, 10, 1, &mut |_| {
+            count += 1;
+            count < 5
+        });
+
+        assert!(!finished);
+        assert_eq!(count, 5);
+    }
+}
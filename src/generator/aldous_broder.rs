@@ -0,0 +1,32 @@
+use super::Grid;
+use crate::{maze::Marker, rng::Rng};
+
+pub fn aldous_broder(grid: &mut Grid, rng: &mut Rng) {
+    let mut visited = vec![false; grid.len()];
+    let mut cell = rng.below(grid.len());
+    let mut remaining = grid.len() - 1;
+
+    visited[cell] = true;
+    grid.mark(cell, Marker::Queued);
+    grid.snapshot();
+
+    while remaining > 0 {
+        let neighbours = grid.neighbours(cell);
+        let next = *rng.choose(&neighbours).unwrap();
+
+        grid.unmark(cell);
+        grid.mark(next, Marker::Queued);
+
+        // only new passages get a step, otherwise the walk would dominate the output
+        if !visited[next] {
+            visited[next] = true;
+            remaining -= 1;
+            grid.carve(cell, next);
+            grid.snapshot();
+        }
+
+        cell = next;
+    }
+
+    grid.unmark(cell);
+}
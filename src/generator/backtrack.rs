@@ -0,0 +1,35 @@
+use super::Grid;
+use crate::{maze::Marker, rng::Rng};
+
+pub fn backtrack(grid: &mut Grid, rng: &mut Rng) {
+    let mut visited = vec![false; grid.len()];
+    let start = rng.below(grid.len());
+    let mut stack = vec![start];
+
+    visited[start] = true;
+    grid.mark(start, Marker::Queued);
+    grid.snapshot();
+
+    while let Some(&cell) = stack.last() {
+        let unvisited: Vec<usize> = grid
+            .neighbours(cell)
+            .into_iter()
+            .filter(|&n| !visited[n])
+            .collect();
+
+        match rng.choose(&unvisited) {
+            Some(&next) => {
+                grid.carve(cell, next);
+                grid.mark(next, Marker::Queued);
+                visited[next] = true;
+                stack.push(next);
+            }
+            None => {
+                grid.unmark(cell);
+                stack.pop();
+            }
+        }
+
+        grid.snapshot();
+    }
+}
@@ -0,0 +1,39 @@
+use super::Grid;
+use crate::{app::BiasMethods, maze::Marker, rng::Rng};
+
+pub fn binary_tree(grid: &mut Grid, rng: &mut Rng, bias: &BiasMethods) {
+    let (north, west) = match bias {
+        BiasMethods::NorthWest => (true, true),
+        BiasMethods::NorthEast => (true, false),
+        BiasMethods::SouthWest => (false, true),
+        BiasMethods::SouthEast => (false, false),
+    };
+
+    for cell in 0..grid.len() {
+        let (x, y) = (grid.x(cell), grid.y(cell));
+        let mut options = Vec::with_capacity(2);
+
+        if north && y > 0 {
+            options.push(cell - grid.width());
+        } else if !north && y + 1 < grid.height() {
+            options.push(cell + grid.width());
+        }
+
+        if west && x > 0 {
+            options.push(cell - 1);
+        } else if !west && x + 1 < grid.width() {
+            options.push(cell + 1);
+        }
+
+        grid.mark(cell, Marker::Queued);
+
+        if let Some(&other) = rng.choose(&options) {
+            grid.carve(cell, other);
+        }
+
+        grid.snapshot();
+        grid.unmark(cell);
+    }
+
+    grid.snapshot();
+}
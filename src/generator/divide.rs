@@ -0,0 +1,47 @@
+use super::Grid;
+use crate::rng::Rng;
+
+pub fn divide(grid: &mut Grid, rng: &mut Rng) {
+    // (x, y, width, height) of the chambers left to split
+    let mut chambers = vec![(0, 0, grid.width(), grid.height())];
+
+    while let Some((x, y, width, height)) = chambers.pop() {
+        if width < 2 && height < 2 {
+            continue;
+        }
+
+        let horizontal = if width == 1 {
+            true
+        } else if height == 1 {
+            false
+        } else if width != height {
+            width < height
+        } else {
+            rng.chance(0.5)
+        };
+
+        if horizontal {
+            let wall_y = y + rng.below(height - 1);
+            let gap = x + rng.below(width);
+
+            for wall_x in (x..x + width).filter(|&wx| wx != gap) {
+                grid.build_wall(grid.cell(wall_x, wall_y), grid.cell(wall_x, wall_y + 1));
+            }
+
+            chambers.push((x, wall_y + 1, width, y + height - wall_y - 1));
+            chambers.push((x, y, width, wall_y - y + 1));
+        } else {
+            let wall_x = x + rng.below(width - 1);
+            let gap = y + rng.below(height);
+
+            for wall_y in (y..y + height).filter(|&wy| wy != gap) {
+                grid.build_wall(grid.cell(wall_x, wall_y), grid.cell(wall_x + 1, wall_y));
+            }
+
+            chambers.push((wall_x + 1, y, x + width - wall_x - 1, height));
+            chambers.push((x, y, wall_x - x + 1, height));
+        }
+
+        grid.snapshot();
+    }
+}
@@ -0,0 +1,71 @@
+use super::Grid;
+use crate::{maze::Marker, rng::Rng};
+
+pub fn eller(grid: &mut Grid, rng: &mut Rng) {
+    let width = grid.width();
+    let height = grid.height();
+    let mut sets = vec![0; width];
+    let mut next_set = 1;
+
+    for y in 0..height {
+        let last_row = y + 1 == height;
+
+        for (x, set) in sets.iter_mut().enumerate() {
+            if *set == 0 {
+                *set = next_set;
+                next_set += 1;
+            }
+            grid.mark(grid.cell(x, y), Marker::Queued);
+        }
+        grid.snapshot();
+
+        // join neighbours in different sets, the last row must join all of them
+        for x in 0..width - 1 {
+            if sets[x] != sets[x + 1] && (last_row || rng.chance(0.5)) {
+                let (keep, old) = (sets[x], sets[x + 1]);
+
+                for set in sets.iter_mut().filter(|s| **s == old) {
+                    *set = keep;
+                }
+
+                grid.carve(grid.cell(x, y), grid.cell(x + 1, y));
+                grid.snapshot();
+            }
+        }
+
+        if last_row {
+            for x in 0..width {
+                grid.unmark(grid.cell(x, y));
+            }
+            grid.snapshot();
+            break;
+        }
+
+        // every set needs at least one passage down
+        let mut next_row = vec![0; width];
+        let mut seen = Vec::new();
+
+        for &set in &sets {
+            if seen.contains(&set) {
+                continue;
+            }
+            seen.push(set);
+
+            let mut members: Vec<usize> = (0..width).filter(|&x| sets[x] == set).collect();
+            rng.shuffle(&mut members);
+            let count = 1 + rng.below(members.len());
+
+            for &x in &members[..count] {
+                grid.carve(grid.cell(x, y), grid.cell(x, y + 1));
+                next_row[x] = set;
+                grid.snapshot();
+            }
+        }
+
+        for x in 0..width {
+            grid.unmark(grid.cell(x, y));
+        }
+
+        sets = next_row;
+    }
+}
@@ -0,0 +1,76 @@
+use super::Grid;
+use crate::{app::TreeSubAlgorithm, maze::Marker, rng::Rng};
+
+pub fn growing_tree(grid: &mut Grid, rng: &mut Rng, method: &TreeSubAlgorithm) {
+    let mut visited = vec![false; grid.len()];
+    let start = rng.below(grid.len());
+    let mut active = vec![start];
+
+    visited[start] = true;
+    grid.mark(start, Marker::Queued);
+    grid.snapshot();
+
+    while !active.is_empty() {
+        let index = select(method, active.len(), rng);
+        let cell = active[index];
+        let unvisited: Vec<usize> = grid
+            .neighbours(cell)
+            .into_iter()
+            .filter(|&n| !visited[n])
+            .collect();
+
+        match rng.choose(&unvisited) {
+            Some(&next) => {
+                grid.carve(cell, next);
+                grid.mark(next, Marker::Queued);
+                visited[next] = true;
+                active.push(next);
+            }
+            None => {
+                grid.unmark(cell);
+                active.remove(index);
+            }
+        }
+
+        grid.snapshot();
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Pick {
+    Newest,
+    Middle,
+    Oldest,
+    Random,
+}
+
+// For the mixed methods the ratio is the chance of using the first method.
+fn select(method: &TreeSubAlgorithm, len: usize, rng: &mut Rng) -> usize {
+    let pick = match method {
+        TreeSubAlgorithm::Newest => Pick::Newest,
+        TreeSubAlgorithm::Middle => Pick::Middle,
+        TreeSubAlgorithm::Oldest => Pick::Oldest,
+        TreeSubAlgorithm::Random => Pick::Random,
+        TreeSubAlgorithm::NewestMiddle(ratio) => mix(rng, *ratio, Pick::Newest, Pick::Middle),
+        TreeSubAlgorithm::NewestOldest(ratio) => mix(rng, *ratio, Pick::Newest, Pick::Oldest),
+        TreeSubAlgorithm::NewestRandom(ratio) => mix(rng, *ratio, Pick::Newest, Pick::Random),
+        TreeSubAlgorithm::MiddleOldest(ratio) => mix(rng, *ratio, Pick::Middle, Pick::Oldest),
+        TreeSubAlgorithm::MiddleRandom(ratio) => mix(rng, *ratio, Pick::Middle, Pick::Random),
+        TreeSubAlgorithm::OldestRandom(ratio) => mix(rng, *ratio, Pick::Oldest, Pick::Random),
+    };
+
+    match pick {
+        Pick::Newest => len - 1,
+        Pick::Middle => len / 2,
+        Pick::Oldest => 0,
+        Pick::Random => rng.below(len),
+    }
+}
+
+fn mix(rng: &mut Rng, ratio: f64, first: Pick, second: Pick) -> Pick {
+    if rng.chance(ratio) {
+        first
+    } else {
+        second
+    }
+}
@@ -0,0 +1,57 @@
+use super::Grid;
+use crate::{maze::Marker, rng::Rng};
+
+pub fn hunt_and_kill(grid: &mut Grid, rng: &mut Rng) {
+    let mut visited = vec![false; grid.len()];
+    let mut cell = rng.below(grid.len());
+    // every cell in the rows above this one has been visited
+    let mut row = 0;
+
+    visited[cell] = true;
+    grid.mark(cell, Marker::Queued);
+    grid.snapshot();
+
+    loop {
+        let unvisited: Vec<usize> = grid
+            .neighbours(cell)
+            .into_iter()
+            .filter(|&n| !visited[n])
+            .collect();
+
+        if let Some(&next) = rng.choose(&unvisited) {
+            grid.carve(cell, next);
+            grid.unmark(cell);
+            grid.mark(next, Marker::Queued);
+            visited[next] = true;
+            cell = next;
+            grid.snapshot();
+            continue;
+        }
+
+        grid.unmark(cell);
+
+        while row < grid.height() && (0..grid.width()).all(|x| visited[grid.cell(x, row)]) {
+            row += 1;
+        }
+
+        // hunt for the first unvisited cell that borders the maze
+        let target = (grid.cell(0, row)..grid.len())
+            .find(|&c| !visited[c] && grid.neighbours(c).iter().any(|&n| visited[n]));
+
+        let Some(target) = target else {
+            break;
+        };
+
+        let linked: Vec<usize> = grid
+            .neighbours(target)
+            .into_iter()
+            .filter(|&n| visited[n])
+            .collect();
+
+        grid.carve(target, *rng.choose(&linked).unwrap());
+        grid.mark(target, Marker::Queued);
+        visited[target] = true;
+        cell = target;
+        grid.snapshot();
+    }
+}
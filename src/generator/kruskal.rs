@@ -0,0 +1,60 @@
+use super::Grid;
+use crate::rng::Rng;
+
+pub fn kruskal(grid: &mut Grid, rng: &mut Rng) {
+    let mut edges = Vec::with_capacity(2 * grid.len());
+
+    for cell in 0..grid.len() {
+        if grid.x(cell) + 1 < grid.width() {
+            edges.push((cell, cell + 1));
+        }
+        if grid.y(cell) + 1 < grid.height() {
+            edges.push((cell, cell + grid.width()));
+        }
+    }
+
+    rng.shuffle(&mut edges);
+
+    let mut sets = DisjointSet::new(grid.len());
+
+    for (a, b) in edges {
+        if sets.union(a, b) {
+            grid.carve(a, b);
+            grid.snapshot();
+        }
+    }
+}
+
+struct DisjointSet {
+    parents: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(size: usize) -> DisjointSet {
+        DisjointSet {
+            parents: (0..size).collect(),
+        }
+    }
+
+    fn find(&mut self, mut node: usize) -> usize {
+        while self.parents[node] != node {
+            self.parents[node] = self.parents[self.parents[node]];
+            node = self.parents[node];
+        }
+
+        node
+    }
+
+    // returns false when both nodes were already in the same set
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+
+        if a == b {
+            return false;
+        }
+
+        self.parents[b] = a;
+        true
+    }
+}
@@ -0,0 +1,47 @@
+use super::Grid;
+use crate::{maze::Marker, rng::Rng};
+
+pub fn prim(grid: &mut Grid, rng: &mut Rng) {
+    let mut in_maze = vec![false; grid.len()];
+    let mut queued = vec![false; grid.len()];
+    let mut frontier = Vec::new();
+
+    let start = rng.below(grid.len());
+    in_maze[start] = true;
+    add_frontier(grid, start, &in_maze, &mut queued, &mut frontier);
+    grid.snapshot();
+
+    while !frontier.is_empty() {
+        let cell = frontier.swap_remove(rng.below(frontier.len()));
+        let linked: Vec<usize> = grid
+            .neighbours(cell)
+            .into_iter()
+            .filter(|&n| in_maze[n])
+            .collect();
+
+        if let Some(&other) = rng.choose(&linked) {
+            grid.carve(cell, other);
+        }
+
+        in_maze[cell] = true;
+        grid.unmark(cell);
+        add_frontier(grid, cell, &in_maze, &mut queued, &mut frontier);
+        grid.snapshot();
+    }
+}
+
+fn add_frontier(
+    grid: &mut Grid,
+    cell: usize,
+    in_maze: &[bool],
+    queued: &mut [bool],
+    frontier: &mut Vec<usize>,
+) {
+    for neighbour in grid.neighbours(cell) {
+        if !in_maze[neighbour] && !queued[neighbour] {
+            queued[neighbour] = true;
+            grid.mark(neighbour, Marker::Queued);
+            frontier.push(neighbour);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use super::Grid;
+use crate::{maze::Marker, rng::Rng};
+
+pub fn sidewinder(grid: &mut Grid, rng: &mut Rng) {
+    let width = grid.width();
+
+    for y in 0..grid.height() {
+        let mut run = Vec::new();
+
+        for x in 0..width {
+            let cell = grid.cell(x, y);
+            run.push(cell);
+            grid.mark(cell, Marker::Queued);
+
+            let close_run = x + 1 == width || (y > 0 && rng.chance(0.5));
+
+            if close_run {
+                if y > 0 {
+                    let up = *rng.choose(&run).unwrap();
+                    grid.carve(up, up - width);
+                }
+
+                for cell in run.drain(..) {
+                    grid.unmark(cell);
+                }
+            } else {
+                grid.carve(cell, cell + 1);
+            }
+
+            grid.snapshot();
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use super::Grid;
+use crate::{maze::Marker, rng::Rng};
+
+pub fn wilson(grid: &mut Grid, rng: &mut Rng) {
+    let mut visited = vec![false; grid.len()];
+    let mut position: Vec<Option<usize>> = vec![None; grid.len()];
+
+    // the cells not in the maze yet, with where each one is in that list
+    let mut unvisited: Vec<usize> = (0..grid.len()).collect();
+    let mut slot: Vec<usize> = (0..grid.len()).collect();
+    let mut visit = |cell: usize, unvisited: &mut Vec<usize>| {
+        let index = slot[cell];
+        unvisited.swap_remove(index);
+
+        if let Some(&moved) = unvisited.get(index) {
+            slot[moved] = index;
+        }
+    };
+
+    let first = rng.below(grid.len());
+    visited[first] = true;
+    visit(first, &mut unvisited);
+
+    while let Some(&start) = rng.choose(&unvisited) {
+        // loop-erased random walk until it reaches the maze
+        let mut path = vec![start];
+        let mut cell = start;
+        position[start] = Some(0);
+        grid.mark(start, Marker::Queued);
+
+        while !visited[cell] {
+            let neighbours = grid.neighbours(cell);
+            let next = *rng.choose(&neighbours).unwrap();
+
+            if let Some(index) = position[next] {
+                for erased in path.drain(index + 1..) {
+                    position[erased] = None;
+                    grid.unmark(erased);
+                }
+            } else {
+                position[next] = Some(path.len());
+                path.push(next);
+
+                if !visited[next] {
+                    grid.mark(next, Marker::Queued);
+                }
+            }
+
+            cell = next;
+        }
+
+        grid.snapshot();
+
+        for pair in path.windows(2) {
+            grid.carve(pair[0], pair[1]);
+        }
+
+        for &cell in &path {
+            position[cell] = None;
+            grid.unmark(cell);
+
+            if !visited[cell] {
+                visited[cell] = true;
+                visit(cell, &mut unvisited);
+            }
+        }
+
+        grid.snapshot();
+    }
+}
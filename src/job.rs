@@ -0,0 +1,199 @@
+use std::{
+    io,
+    sync::{
+        mpsc::{self, Receiver, TryRecvError},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    app::{GenAlgorithms, SolveAlgorithms},
+    backend::{BackendError, Cancel, MazeBackend, StepSink, STEP_BACKLOG},
+    maze::Maze,
+};
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+#[derive(PartialEq, Clone)]
+pub enum JobKind {
+    /// `seed` is `None` when the backend ignores it, as the maze can't be made again.
+    Generate {
+        seed: Option<u64>,
+        generator: String,
+    },
+    Solve {
+        solver: String,
+    },
+}
+
+pub enum JobEvent {
+    Step(Maze),
+    Finished(Result<Maze, BackendError>),
+}
+
+/// A generator or solver run happening on a worker thread.
+pub struct Job {
+    pub kind: JobKind,
+    pub label: String,
+    started: Instant,
+    cancel: Cancel,
+    receiver: Receiver<JobEvent>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Job {
+    pub fn generate(
+        backend: Arc<dyn MazeBackend>,
+        algorithm: GenAlgorithms,
+        width: usize,
+        height: usize,
+        seed: u64,
+    ) -> Job {
+        let generator = algorithm.get_name();
+        let kept_seed = backend.uses_seed().then_some(seed);
+        let label = match kept_seed {
+            Some(seed) => format!("Generating {generator} (seed {seed})"),
+            None => format!("Generating {generator}"),
+        };
+        let kind = JobKind::Generate {
+            seed: kept_seed,
+            generator,
+        };
+
+        Job::spawn(kind, label, move |steps| {
+            backend.generate(&algorithm, width, height, seed, steps)
+        })
+    }
+
+    pub fn solve(backend: Arc<dyn MazeBackend>, maze: Maze, algorithm: SolveAlgorithms) -> Job {
+        let solver = algorithm.get_name();
+        let label = format!("Solving with {solver}");
+
+        Job::spawn(JobKind::Solve { solver }, label, move |steps| {
+            backend.solve(&maze, &algorithm, steps)
+        })
+    }
+
+    fn spawn<F>(kind: JobKind, label: String, run: F) -> Job
+    where
+        F: FnOnce(&mut StepSink) -> Result<Maze, BackendError> + Send + 'static,
+    {
+        let cancel = Cancel::default();
+        let (sender, receiver) = mpsc::sync_channel(STEP_BACKLOG);
+
+        let worker_cancel = cancel.clone();
+        let handle = thread::spawn(move || {
+            let step_sender = sender.clone();
+            let mut on_step = |step| {
+                let _ = step_sender.send(JobEvent::Step(step));
+            };
+            let result = run(&mut StepSink::new(worker_cancel, &mut on_step));
+
+            let _ = sender.send(JobEvent::Finished(result));
+        });
+
+        Job {
+            kind,
+            label,
+            started: Instant::now(),
+            cancel,
+            receiver,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Returns the next step or the final result, without blocking.
+    pub fn try_event(&self) -> Option<JobEvent> {
+        match self.receiver.try_recv() {
+            Ok(event) => Some(event),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(JobEvent::Finished(Err(BackendError::Io(
+                io::Error::other("the worker stopped unexpectedly"),
+            )))),
+        }
+    }
+
+    pub fn spinner(&self) -> char {
+        let frame = self.started.elapsed().as_millis() / 80;
+
+        SPINNER[frame as usize % SPINNER.len()]
+    }
+}
+
+impl Drop for Job {
+    // make sure a child process doesn't outlive the viewer
+    fn drop(&mut self) {
+        self.cancel();
+
+        if let Some(handle) = self.handle.take() {
+            // a worker blocked on a full channel only sees the cancel once there is room
+            while !handle.is_finished() {
+                let _ = self.receiver.recv_timeout(Duration::from_millis(10));
+            }
+
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod job_tests {
+    use super::*;
+    use crate::{
+        backend::{BuiltinBackend, ProcessBackend},
+        workdir::WorkDir,
+    };
+
+    #[test]
+    fn dropping_a_blocked_job_stops_it() {
+        let job = Job::generate(
+            Arc::new(BuiltinBackend),
+            GenAlgorithms::AldousBroder,
+            100,
+            100,
+            1,
+        );
+
+        // nothing takes the steps in, so the worker is soon waiting on a full channel
+        thread::sleep(Duration::from_millis(100));
+        assert!(matches!(job.try_event(), Some(JobEvent::Step(_))));
+
+        let started = Instant::now();
+        drop(job);
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn seeds_are_only_kept_when_used() {
+        let builtin = Job::generate(Arc::new(BuiltinBackend), GenAlgorithms::Prim, 3, 3, 5);
+        let process = Job::generate(
+            Arc::new(ProcessBackend {
+                gen_bin: "false".to_string(),
+                solve_bin: String::new(),
+                files: WorkDir::create(None, None, false).unwrap(),
+            }),
+            GenAlgorithms::Prim,
+            3,
+            3,
+            5,
+        );
+
+        assert!(matches!(
+            builtin.kind,
+            JobKind::Generate { seed: Some(5), .. }
+        ));
+        assert!(matches!(process.kind, JobKind::Generate { seed: None, .. }));
+        assert!(!process.label.contains("seed"));
+    }
+}
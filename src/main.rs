@@ -1,71 +1,139 @@
 use crate::{app::AlgorithmSetting, ui::ui};
 
-use std::{error::Error, fs, io, process::Command, time::Duration};
+use std::{
+    env,
+    error::Error,
+    io,
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use app::{App, BiasMethods, CurrentScreen, GenAlgorithms, SizeSetting, TreeSubAlgorithm};
+use app::{
+    App, BiasMethods, CurrentScreen, GenAlgorithms, SizeSetting, TreeSubAlgorithm, MAX_SIZE,
+};
+use backend::{BackendError, ProcessBackend};
+use browser::FileBrowser;
+use config::{Config, USAGE};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use job::{Job, JobEvent, JobKind};
+use maze::Maze;
+use save::Save;
+use session::Session;
+use setup::Setup;
+use workdir::WorkDir;
 
 use color_eyre::Result;
 use ratatui::{
     backend::{Backend, CrosstermBackend},
+    layout::Rect,
     Terminal,
 };
 
 mod app;
+mod backend;
+mod browser;
+mod config;
+mod export;
+mod generator;
+mod job;
+mod maze;
+mod rng;
+mod save;
+mod session;
+mod setup;
+mod solver;
+mod steps;
 mod ui;
+mod workdir;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    // setup terminal
-    enable_raw_mode()?;
-    let mut stderr = io::stderr();
-    execute!(stderr, EnterAlternateScreen, EnableMouseCapture)?;
+// what the open screen lists
+const MAZE_FILES: &[&str] = &["mz", "steps"];
 
-    let backend = CrosstermBackend::new(stderr);
-    let mut terminal = Terminal::new(backend)?;
+// how many text columns or rows a pan key moves the maze
+const PAN: isize = 4;
 
-    let mut app = App::new();
+// how long to take in steps from a run between draws
+const DRAIN_TIME: Duration = Duration::from_millis(100);
 
-    match std::env::var("MAZE_GEN") {
-        Ok(str) => app.gen_bin = str,
-        Err(err) => {
-            disable_raw_mode()?;
+fn main() -> Result<(), Box<dyn Error>> {
+    // put the terminal back before a panic message is printed
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stderr(), LeaveAlternateScreen, DisableMouseCapture);
+        hook(info);
+    }));
 
-            execute!(
-                terminal.backend_mut(),
-                LeaveAlternateScreen,
-                DisableMouseCapture
-            )?;
-            terminal.show_cursor()?;
+    let args: Vec<String> = env::args().skip(1).collect();
 
-            eprintln!("\"MAZE_GEN\" must be defined to the path of the maze generator.");
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
 
-            return Err(Box::new(err));
+    // report a bad setting before the terminal is taken over
+    let config = match Config::load(args) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            process::exit(2);
         }
     };
 
-    match std::env::var("MAZE_SOLVE") {
-        Ok(str) => app.solve_bin = str,
-        Err(err) => {
-            disable_raw_mode()?;
+    let mut app = App::new();
 
-            execute!(
-                terminal.backend_mut(),
-                LeaveAlternateScreen,
-                DisableMouseCapture
-            )?;
-            terminal.show_cursor()?;
+    config.apply(&mut app);
 
-            eprintln!("\"MAZE_SOLVE\" must be defined to the path of the maze generator.");
+    // the built-in backend is always available, the binaries are optional
+    let gen_bin = config.gen_bin.clone().unwrap_or_default();
+    let solve_bin = config.solve_bin.clone().unwrap_or_default();
 
-            return Err(Box::new(err));
+    install_process_backend(&mut app, &config, gen_bin.clone(), solve_bin.clone())?;
+
+    // walk new users through picking the tools until a config file exists
+    let first_run = config
+        .config_file
+        .as_ref()
+        .is_some_and(|path| !path.exists());
+
+    if first_run && gen_bin.is_empty() && solve_bin.is_empty() {
+        app.setup = Some(Setup::new(gen_bin, solve_bin));
+        app.current_screen = CurrentScreen::Setup;
+    }
+
+    let session_path = session::default_path();
+
+    // offer to pick up where the last session left off, unless a file was asked for
+    if let Some(path) = session_path.as_ref().filter(|path| path.exists()) {
+        if config.open.is_none() && app.setup.is_none() {
+            match Session::load(path) {
+                Ok(session) => {
+                    app.session = Some(session);
+                    app.current_screen = CurrentScreen::Restore;
+                }
+                Err(err) => app.error = Some(format!("Failed to read the last session.\n\n{err}")),
+            }
         }
-    };
+    }
 
-    let res = run_app(&mut terminal, &mut app);
+    // setup terminal
+    enable_raw_mode()?;
+    let mut stderr = io::stderr();
+    execute!(stderr, EnterAlternateScreen, EnableMouseCapture)?;
+
+    let backend = CrosstermBackend::new(stderr);
+    let mut terminal = Terminal::new(backend)?;
+
+    let res = run_app(&mut terminal, &mut app, &config);
 
     disable_raw_mode()?;
 
@@ -76,36 +144,133 @@ fn main() -> Result<(), Box<dyn Error>> {
     )?;
     terminal.show_cursor()?;
 
-    if let Ok(..) = res {
-    } else if let Err(err) = res {
+    if let Err(err) = res {
         println!("{err:?}");
     }
 
+    // a run that is still going isn't part of the session
+    if app.job.take().is_some() {
+        app.abandon_run();
+    }
+
+    if let Some(path) = &session_path {
+        if let Err(err) = Session::save(&app, path) {
+            println!("Failed to save the session to {}: {err}", path.display());
+        }
+    }
+
+    for dir in &app.kept_dirs {
+        println!("Maze files were kept in {}", dir.display());
+    }
+
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
-    terminal.draw(|f| ui(f, app))?;
+fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    config: &Config,
+) -> io::Result<()> {
+    // start with a square maze that fits the window below the key hints
+    let area = terminal.size()?;
+    let (columns, rows) = app.render_mode.cells_in(Rect {
+        height: area.height.saturating_sub(3),
+        ..area
+    });
+    let fits = columns.min(rows);
 
-    app.set_width(app.get_max_size() / 2);
-    app.set_height(app.get_max_size() / 2);
+    app.set_width(config.width.unwrap_or(fits / 2));
+    app.set_height(config.height.unwrap_or(fits / 2));
 
     app.clear_maze();
 
+    if let Some(path) = &config.open {
+        if let Err(err) = app.open_file(path) {
+            app.error = Some(format!("Failed to open {}.\n\n{err}", path.display()));
+        }
+    }
+
+    let mut last_frame = Instant::now();
+
     loop {
+        app.poll_steps();
         terminal.draw(|f| ui(f, app))?;
 
-        if let Event::Key(key) = event::read()? {
+        // a worker refills the channel as soon as a step is taken, so stop after a while
+        // and leave the rest for after the next draw
+        let started = Instant::now();
+
+        while let Some(event) = app
+            .job
+            .as_ref()
+            .filter(|_| started.elapsed() < DRAIN_TIME)
+            .and_then(|job| job.try_event())
+        {
+            match event {
+                JobEvent::Step(step) => app.push_step(step),
+                JobEvent::Finished(result) => finish_job(app, result),
+            }
+        }
+
+        let period = Duration::from_millis(app.get_period());
+
+        if app.playing && last_frame.elapsed() >= period {
+            last_frame = Instant::now();
+
+            // keep waiting at the end while the run is still producing steps
+            if !app.step_forward() && app.job.is_none() {
+                app.playing = false;
+            }
+        }
+
+        // keep redrawing the spinner and playback while they run
+        let timeout = if app.playing {
+            period.saturating_sub(last_frame.elapsed())
+        } else if app.job.is_some() || app.is_indexing() {
+            Duration::from_millis(80)
+        } else {
+            Duration::from_secs(1)
+        };
+
+        if !event::poll(timeout)? {
+            continue;
+        }
+
+        let event = event::read()?;
+
+        if let Event::Mouse(mouse) = event {
+            if app.error.is_none() && matches!(app.current_screen, CurrentScreen::Main) {
+                app.viewport.mouse(mouse);
+            }
+            continue;
+        }
+
+        if let Event::Key(key) = event {
             if key.kind == KeyEventKind::Release {
                 continue;
             }
 
+            // the error popup takes every key until it is dismissed
+            if app.error.is_some() {
+                if matches!(key.code, KeyCode::Esc | KeyCode::Enter) {
+                    app.error = None;
+                }
+                continue;
+            }
+
+            if let (Some(job), CurrentScreen::Main) = (&app.job, &app.current_screen) {
+                if key.code == KeyCode::Esc {
+                    job.cancel();
+                    continue;
+                }
+            }
+
             match app.current_screen {
                 CurrentScreen::Main => match key.code {
                     KeyCode::Char('q') | KeyCode::Char('Q') => {
                         return Ok(());
                     }
-                    KeyCode::Char('z') | KeyCode::Char('Z') => {
+                    KeyCode::Char('z') | KeyCode::Char('Z') if app.job.is_none() => {
                         app.current_screen = CurrentScreen::Size;
                         app.tmp = match app.size_setting {
                             SizeSetting::Width => app.get_width(),
@@ -119,73 +284,89 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                     KeyCode::Char('a') | KeyCode::Char('A') => {
                         app.current_screen = CurrentScreen::Algorithm;
                     }
-                    KeyCode::Char('g') | KeyCode::Char('G') => {
-                        let mut args =
-                            Vec::from(["-q", "-v", "maze.steps", "-a"].map(|s| s.to_string()));
+                    KeyCode::Char('u') | KeyCode::Char('U') if app.job.is_none() => {
+                        let (gen_bin, solve_bin) = app.get_backend().binaries();
+                        app.setup = Some(Setup::new(gen_bin, solve_bin));
+                        app.current_screen = CurrentScreen::Setup;
+                    }
+                    KeyCode::Char('o') | KeyCode::Char('O') if app.job.is_none() => {
+                        let start = env::current_dir().unwrap_or_default();
 
-                        for arg in app.gen_algorithm.to_string().split(' ') {
-                            args.push(arg.to_string());
+                        match FileBrowser::open_filtered(&start, MAZE_FILES) {
+                            Ok(browser) => {
+                                app.browser = Some(browser);
+                                app.current_screen = CurrentScreen::Open;
+                            }
+                            Err(err) => {
+                                app.error = Some(format!("Failed to open the directory.\n\n{err}"))
+                            }
                         }
-
-                        args.push(app.get_width().to_string());
-                        args.push(app.get_height().to_string());
-
-                        let output = Command::new(&app.gen_bin)
-                            .args(args)
-                            .output()
-                            .expect("Failed to call maze generator.");
-                        app.maze = String::from_utf8(output.stdout).unwrap();
-                        app.load_steps("maze.steps");
-                        app.set_step_val(0);
-                        app.has_generated = true;
-                        let _ = fs::write("maze.mz", &app.maze);
-                    }
-                    KeyCode::Char('s') | KeyCode::Char('S') => {
-                        if !app.has_generated {
-                            continue;
+                    }
+                    KeyCode::Char('e') | KeyCode::Char('E') => {
+                        app.seed_tmp = app.seed;
+                        app.current_screen = CurrentScreen::Seed;
+                    }
+                    KeyCode::Char('b') | KeyCode::Char('B') => {
+                        app.set_backend(app.get_backend_val() + 1);
+                    }
+                    KeyCode::Char('g') | KeyCode::Char('G') if app.job.is_none() => {
+                        app.begin_run();
+                        app.job = Some(Job::generate(
+                            app.get_backend(),
+                            app.gen_algorithm.clone(),
+                            app.get_width(),
+                            app.get_height(),
+                            app.seed.unwrap_or_else(rng::random_seed),
+                        ));
+                    }
+                    KeyCode::Char('s')
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && app.job.is_none() =>
+                    {
+                        app.save = Some(Save::new());
+                        app.current_screen = CurrentScreen::Save;
+                    }
+                    KeyCode::Char('s') | KeyCode::Char('S')
+                        if app.job.is_none() && app.has_generated =>
+                    {
+                        if let Some(maze) = app.generated_maze.clone() {
+                            app.begin_run();
+                            app.job = Some(Job::solve(
+                                app.get_backend(),
+                                maze,
+                                app.solve_algorithm.clone(),
+                            ));
                         }
+                    }
+                    KeyCode::Char('r') | KeyCode::Char('R') if app.can_step() => {
+                        app.stop_following();
 
-                        let mut args =
-                            Vec::from(["-q", "-v", "maze.steps", "-i", "maze.mz", "-a"].map(|s| s.to_string()));
-
-                        for arg in app.solve_algorithm.to_string().split(' ') {
-                            args.push(arg.to_string());
+                        if app.playing {
+                            app.playing = false;
+                        } else {
+                            if app.job.is_none() && app.get_step_val() == app.maze_steps.len() - 1 {
+                                app.set_step_val(0);
+                                app.show_step();
+                            }
+                            app.playing = true;
+                            app.viewport.follow = true;
+                            last_frame = Instant::now();
                         }
-
-                        args.push(app.get_width().to_string());
-                        args.push(app.get_height().to_string());
-
-                        let output = Command::new(&app.solve_bin)
-                            .args(args)
-                            .output()
-                            .expect("Failed to call maze solver.");
-                        app.maze = String::from_utf8(output.stdout).unwrap();
-                        app.load_steps("maze.steps");
-                        app.set_step_val(0);
                     }
-                    KeyCode::Char('r') | KeyCode::Char('R') => {
-                        if app.get_step_val() == app.maze_steps.len() - 1 {
-                            app.set_step_val(0);
-                        }
-                        let start = app.get_step_val();
-                        for i in start..app.maze_steps.len() {
-                            app.set_step_val(i);
-                            app.maze = app.get_step().clone();
-                            std::thread::sleep(Duration::from_millis(app.get_period()));
-                            terminal.draw(|f| ui(f, app))?;
-                        }
+                    KeyCode::Left if app.can_step() => {
+                        app.stop_following();
+                        app.step_back();
                     }
-                    KeyCode::Left => {
-                        if app.has_generated && app.get_step_val() > 0 {
-                            app.set_step_val(app.get_step_val() - 1);
-                            app.maze = app.get_step().clone();
-                        }
+                    KeyCode::Right if app.can_step() => {
+                        app.stop_following();
+                        app.step_forward();
                     }
-                    KeyCode::Right => {
-                        if app.has_generated && app.get_step_val() < app.maze_steps.len() - 1 {
-                            app.set_step_val(app.get_step_val() + 1);
-                            app.maze = app.get_step().clone();
-                        }
+                    KeyCode::Char('h') | KeyCode::Char('H') => app.viewport.pan(-PAN, 0),
+                    KeyCode::Char('j') | KeyCode::Char('J') => app.viewport.pan(0, PAN),
+                    KeyCode::Char('k') | KeyCode::Char('K') => app.viewport.pan(0, -PAN),
+                    KeyCode::Char('l') | KeyCode::Char('L') => app.viewport.pan(PAN, 0),
+                    KeyCode::Char('f') | KeyCode::Char('F') => app.viewport.follow = true,
+                    KeyCode::Char('v') | KeyCode::Char('V') => {
+                        app.render_mode = app.render_mode.next();
                     }
                     _ => {}
                 },
@@ -220,34 +401,34 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                         }
                     }
                     KeyCode::Char('0') => {
-                        app.tmp = enter_value(0, app.tmp, app.get_max_size());
+                        app.tmp = enter_value(0, app.tmp, MAX_SIZE);
                     }
                     KeyCode::Char('1') => {
-                        app.tmp = enter_value(1, app.tmp, app.get_max_size());
+                        app.tmp = enter_value(1, app.tmp, MAX_SIZE);
                     }
                     KeyCode::Char('2') => {
-                        app.tmp = enter_value(2, app.tmp, app.get_max_size());
+                        app.tmp = enter_value(2, app.tmp, MAX_SIZE);
                     }
                     KeyCode::Char('3') => {
-                        app.tmp = enter_value(3, app.tmp, app.get_max_size());
+                        app.tmp = enter_value(3, app.tmp, MAX_SIZE);
                     }
                     KeyCode::Char('4') => {
-                        app.tmp = enter_value(4, app.tmp, app.get_max_size());
+                        app.tmp = enter_value(4, app.tmp, MAX_SIZE);
                     }
                     KeyCode::Char('5') => {
-                        app.tmp = enter_value(5, app.tmp, app.get_max_size());
+                        app.tmp = enter_value(5, app.tmp, MAX_SIZE);
                     }
                     KeyCode::Char('6') => {
-                        app.tmp = enter_value(6, app.tmp, app.get_max_size());
+                        app.tmp = enter_value(6, app.tmp, MAX_SIZE);
                     }
                     KeyCode::Char('7') => {
-                        app.tmp = enter_value(7, app.tmp, app.get_max_size());
+                        app.tmp = enter_value(7, app.tmp, MAX_SIZE);
                     }
                     KeyCode::Char('8') => {
-                        app.tmp = enter_value(8, app.tmp, app.get_max_size());
+                        app.tmp = enter_value(8, app.tmp, MAX_SIZE);
                     }
                     KeyCode::Char('9') => {
-                        app.tmp = enter_value(9, app.tmp, app.get_max_size());
+                        app.tmp = enter_value(9, app.tmp, MAX_SIZE);
                     }
                     KeyCode::Enter => match app.size_setting {
                         SizeSetting::Width => app.set_width(app.tmp),
@@ -296,6 +477,75 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                     }
                     _ => {}
                 },
+                CurrentScreen::Seed => match key.code {
+                    KeyCode::Esc => {
+                        app.seed = app.seed_tmp;
+                        app.current_screen = CurrentScreen::Main;
+                    }
+                    KeyCode::Char('r') | KeyCode::Char('R') => {
+                        app.seed_tmp = None;
+                    }
+                    KeyCode::Char('c') | KeyCode::Char('C') if app.last_seed.is_some() => {
+                        app.seed_tmp = app.last_seed;
+                    }
+                    KeyCode::Backspace => {
+                        app.seed_tmp = app.seed_tmp.map(|seed| seed / 10).filter(|&seed| seed > 0);
+                    }
+                    KeyCode::Char('0') => {
+                        app.seed_tmp = enter_seed(0, app.seed_tmp);
+                    }
+                    KeyCode::Char('1') => {
+                        app.seed_tmp = enter_seed(1, app.seed_tmp);
+                    }
+                    KeyCode::Char('2') => {
+                        app.seed_tmp = enter_seed(2, app.seed_tmp);
+                    }
+                    KeyCode::Char('3') => {
+                        app.seed_tmp = enter_seed(3, app.seed_tmp);
+                    }
+                    KeyCode::Char('4') => {
+                        app.seed_tmp = enter_seed(4, app.seed_tmp);
+                    }
+                    KeyCode::Char('5') => {
+                        app.seed_tmp = enter_seed(5, app.seed_tmp);
+                    }
+                    KeyCode::Char('6') => {
+                        app.seed_tmp = enter_seed(6, app.seed_tmp);
+                    }
+                    KeyCode::Char('7') => {
+                        app.seed_tmp = enter_seed(7, app.seed_tmp);
+                    }
+                    KeyCode::Char('8') => {
+                        app.seed_tmp = enter_seed(8, app.seed_tmp);
+                    }
+                    KeyCode::Char('9') => {
+                        app.seed_tmp = enter_seed(9, app.seed_tmp);
+                    }
+                    KeyCode::Enter => {
+                        app.seed = app.seed_tmp;
+                    }
+                    _ => {}
+                },
+                CurrentScreen::Setup => setup_key(app, config, key),
+                CurrentScreen::Open => open_key(app, key),
+                CurrentScreen::Save => save_key(app, key),
+                CurrentScreen::Restore => match key.code {
+                    KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        app.current_screen = CurrentScreen::Main;
+
+                        if let Some(session) = app.session.take() {
+                            if let Err(err) = session.restore(app) {
+                                app.error =
+                                    Some(format!("Failed to restore the last session.\n\n{err}"));
+                            }
+                        }
+                    }
+                    KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+                        app.session = None;
+                        app.current_screen = CurrentScreen::Main;
+                    }
+                    _ => {}
+                },
                 CurrentScreen::Algorithm => match key.code {
                     KeyCode::Esc => {
                         app.current_screen = CurrentScreen::Main;
@@ -342,139 +592,115 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                             }
                         }
                     },
-                    KeyCode::Right => {
-                        if app.algorithm_setting == AlgorithmSetting::Generator {
-                            if let GenAlgorithms::GrowingTree(method) = &app.gen_algorithm {
-                                app.gen_algorithm = match method {
-                                    TreeSubAlgorithm::Newest => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::Middle)
-                                    }
-                                    TreeSubAlgorithm::Middle => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::Oldest)
-                                    }
-                                    TreeSubAlgorithm::Oldest => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::Random)
-                                    }
-                                    TreeSubAlgorithm::Random => GenAlgorithms::GrowingTree(
-                                        TreeSubAlgorithm::NewestMiddle(app.get_ratio()),
-                                    ),
-                                    TreeSubAlgorithm::NewestMiddle(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::NewestOldest(
-                                            app.get_ratio(),
-                                        ))
-                                    }
-                                    TreeSubAlgorithm::NewestOldest(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::NewestRandom(
-                                            app.get_ratio(),
-                                        ))
-                                    }
-                                    TreeSubAlgorithm::NewestRandom(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::MiddleOldest(
-                                            app.get_ratio(),
-                                        ))
-                                    }
-                                    TreeSubAlgorithm::MiddleOldest(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::MiddleRandom(
-                                            app.get_ratio(),
-                                        ))
-                                    }
-                                    TreeSubAlgorithm::MiddleRandom(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::OldestRandom(
-                                            app.get_ratio(),
-                                        ))
-                                    }
-                                    TreeSubAlgorithm::OldestRandom(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::Newest)
-                                    }
-                                };
-                                app.gen_algo_lookup[app.gen_list_state.selected().unwrap()] =
-                                    app.gen_algorithm.clone();
-                            } else if let GenAlgorithms::BinaryTree(bias) = &app.gen_algorithm {
-                                app.gen_algorithm = match bias {
-                                    BiasMethods::NorthWest => {
-                                        GenAlgorithms::BinaryTree(BiasMethods::NorthEast)
-                                    }
-                                    BiasMethods::NorthEast => {
-                                        GenAlgorithms::BinaryTree(BiasMethods::SouthWest)
-                                    }
-                                    BiasMethods::SouthWest => {
-                                        GenAlgorithms::BinaryTree(BiasMethods::SouthEast)
-                                    }
-                                    BiasMethods::SouthEast => {
-                                        GenAlgorithms::BinaryTree(BiasMethods::NorthWest)
-                                    }
-                                };
-
-                                app.gen_algo_lookup[app.gen_list_state.selected().unwrap()] =
-                                    app.gen_algorithm.clone();
-                            }
+                    KeyCode::Right if app.algorithm_setting == AlgorithmSetting::Generator => {
+                        if let GenAlgorithms::GrowingTree(method) = &app.gen_algorithm {
+                            app.gen_algorithm = match method {
+                                TreeSubAlgorithm::Newest => {
+                                    GenAlgorithms::GrowingTree(TreeSubAlgorithm::Middle)
+                                }
+                                TreeSubAlgorithm::Middle => {
+                                    GenAlgorithms::GrowingTree(TreeSubAlgorithm::Oldest)
+                                }
+                                TreeSubAlgorithm::Oldest => {
+                                    GenAlgorithms::GrowingTree(TreeSubAlgorithm::Random)
+                                }
+                                TreeSubAlgorithm::Random => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::NewestMiddle(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::NewestMiddle(_) => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::NewestOldest(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::NewestOldest(_) => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::NewestRandom(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::NewestRandom(_) => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::MiddleOldest(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::MiddleOldest(_) => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::MiddleRandom(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::MiddleRandom(_) => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::OldestRandom(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::OldestRandom(_) => {
+                                    GenAlgorithms::GrowingTree(TreeSubAlgorithm::Newest)
+                                }
+                            };
+                            app.gen_algo_lookup[app.gen_list_state.selected().unwrap()] =
+                                app.gen_algorithm.clone();
+                        } else if let GenAlgorithms::BinaryTree(bias) = &app.gen_algorithm {
+                            app.gen_algorithm = match bias {
+                                BiasMethods::NorthWest => {
+                                    GenAlgorithms::BinaryTree(BiasMethods::NorthEast)
+                                }
+                                BiasMethods::NorthEast => {
+                                    GenAlgorithms::BinaryTree(BiasMethods::SouthWest)
+                                }
+                                BiasMethods::SouthWest => {
+                                    GenAlgorithms::BinaryTree(BiasMethods::SouthEast)
+                                }
+                                BiasMethods::SouthEast => {
+                                    GenAlgorithms::BinaryTree(BiasMethods::NorthWest)
+                                }
+                            };
+
+                            app.gen_algo_lookup[app.gen_list_state.selected().unwrap()] =
+                                app.gen_algorithm.clone();
                         }
                     }
-                    KeyCode::Left => {
-                        if app.algorithm_setting == AlgorithmSetting::Generator {
-                            if let GenAlgorithms::GrowingTree(method) = &app.gen_algorithm {
-                                app.gen_algorithm = match method {
-                                    TreeSubAlgorithm::Newest => GenAlgorithms::GrowingTree(
-                                        TreeSubAlgorithm::OldestRandom(app.get_ratio()),
-                                    ),
-                                    TreeSubAlgorithm::Middle => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::Newest)
-                                    }
-                                    TreeSubAlgorithm::Oldest => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::Middle)
-                                    }
-                                    TreeSubAlgorithm::Random => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::Oldest)
-                                    }
-                                    TreeSubAlgorithm::NewestMiddle(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::Random)
-                                    }
-                                    TreeSubAlgorithm::NewestOldest(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::NewestMiddle(
-                                            app.get_ratio(),
-                                        ))
-                                    }
-                                    TreeSubAlgorithm::NewestRandom(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::NewestOldest(
-                                            app.get_ratio(),
-                                        ))
-                                    }
-                                    TreeSubAlgorithm::MiddleOldest(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::NewestRandom(
-                                            app.get_ratio(),
-                                        ))
-                                    }
-                                    TreeSubAlgorithm::MiddleRandom(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::MiddleOldest(
-                                            app.get_ratio(),
-                                        ))
-                                    }
-                                    TreeSubAlgorithm::OldestRandom(_) => {
-                                        GenAlgorithms::GrowingTree(TreeSubAlgorithm::MiddleRandom(
-                                            app.get_ratio(),
-                                        ))
-                                    }
-                                };
-                                app.gen_algo_lookup[app.gen_list_state.selected().unwrap()] =
-                                    app.gen_algorithm.clone();
-                            } else if let GenAlgorithms::BinaryTree(bias) = &app.gen_algorithm {
-                                app.gen_algorithm = match bias {
-                                    BiasMethods::NorthWest => {
-                                        GenAlgorithms::BinaryTree(BiasMethods::SouthEast)
-                                    }
-                                    BiasMethods::NorthEast => {
-                                        GenAlgorithms::BinaryTree(BiasMethods::NorthWest)
-                                    }
-                                    BiasMethods::SouthWest => {
-                                        GenAlgorithms::BinaryTree(BiasMethods::NorthEast)
-                                    }
-                                    BiasMethods::SouthEast => {
-                                        GenAlgorithms::BinaryTree(BiasMethods::SouthWest)
-                                    }
-                                };
-                                app.gen_algo_lookup[app.gen_list_state.selected().unwrap()] =
-                                    app.gen_algorithm.clone();
-                            }
+                    KeyCode::Left if app.algorithm_setting == AlgorithmSetting::Generator => {
+                        if let GenAlgorithms::GrowingTree(method) = &app.gen_algorithm {
+                            app.gen_algorithm = match method {
+                                TreeSubAlgorithm::Newest => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::OldestRandom(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::Middle => {
+                                    GenAlgorithms::GrowingTree(TreeSubAlgorithm::Newest)
+                                }
+                                TreeSubAlgorithm::Oldest => {
+                                    GenAlgorithms::GrowingTree(TreeSubAlgorithm::Middle)
+                                }
+                                TreeSubAlgorithm::Random => {
+                                    GenAlgorithms::GrowingTree(TreeSubAlgorithm::Oldest)
+                                }
+                                TreeSubAlgorithm::NewestMiddle(_) => {
+                                    GenAlgorithms::GrowingTree(TreeSubAlgorithm::Random)
+                                }
+                                TreeSubAlgorithm::NewestOldest(_) => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::NewestMiddle(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::NewestRandom(_) => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::NewestOldest(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::MiddleOldest(_) => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::NewestRandom(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::MiddleRandom(_) => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::MiddleOldest(app.get_ratio()),
+                                ),
+                                TreeSubAlgorithm::OldestRandom(_) => GenAlgorithms::GrowingTree(
+                                    TreeSubAlgorithm::MiddleRandom(app.get_ratio()),
+                                ),
+                            };
+                            app.gen_algo_lookup[app.gen_list_state.selected().unwrap()] =
+                                app.gen_algorithm.clone();
+                        } else if let GenAlgorithms::BinaryTree(bias) = &app.gen_algorithm {
+                            app.gen_algorithm = match bias {
+                                BiasMethods::NorthWest => {
+                                    GenAlgorithms::BinaryTree(BiasMethods::SouthEast)
+                                }
+                                BiasMethods::NorthEast => {
+                                    GenAlgorithms::BinaryTree(BiasMethods::NorthWest)
+                                }
+                                BiasMethods::SouthWest => {
+                                    GenAlgorithms::BinaryTree(BiasMethods::NorthEast)
+                                }
+                                BiasMethods::SouthEast => {
+                                    GenAlgorithms::BinaryTree(BiasMethods::SouthWest)
+                                }
+                            };
+                            app.gen_algo_lookup[app.gen_list_state.selected().unwrap()] =
+                                app.gen_algorithm.clone();
                         }
                     }
                     KeyCode::Char('0') => {
@@ -555,6 +781,215 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
     }
 }
 
+fn setup_key(app: &mut App, config: &Config, key: KeyEvent) {
+    let setup = app.setup.as_mut().unwrap();
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+    if let Some(browser) = &mut setup.browser {
+        match key.code {
+            KeyCode::Esc => setup.browser = None,
+            KeyCode::Down => browser.next(),
+            KeyCode::Up => browser.previous(),
+            KeyCode::Backspace | KeyCode::Left => {
+                if let Err(err) = browser.parent() {
+                    app.error = Some(format!("Failed to open the directory.\n\n{err}"));
+                }
+            }
+            KeyCode::Enter => match browser.select() {
+                Ok(Some(path)) => {
+                    setup.edit(|bin| *bin = path.to_string_lossy().into_owned());
+                    setup.browser = None;
+                }
+                Ok(None) => {}
+                Err(err) => app.error = Some(format!("Failed to open the directory.\n\n{err}")),
+            },
+            _ => {}
+        }
+
+        return;
+    }
+
+    match key.code {
+        KeyCode::Esc => {
+            app.setup = None;
+            app.current_screen = CurrentScreen::Main;
+        }
+        KeyCode::Tab | KeyCode::Up | KeyCode::Down => setup.switch_field(),
+        KeyCode::Enter => setup.probe(),
+        KeyCode::Backspace => setup.edit(|bin| {
+            bin.pop();
+        }),
+        KeyCode::Char('o') if ctrl => {
+            let start = match setup.input() {
+                "" => env::current_dir().unwrap_or_default(),
+                bin => PathBuf::from(bin),
+            };
+
+            match FileBrowser::open(&start).or_else(|_| FileBrowser::open(Path::new("."))) {
+                Ok(browser) => setup.browser = Some(browser),
+                Err(err) => app.error = Some(format!("Failed to open the directory.\n\n{err}")),
+            }
+        }
+        KeyCode::Char('b') if ctrl => setup.edit(|bin| bin.clear()),
+        KeyCode::Char('s') if ctrl => {
+            if !setup.probe_all() {
+                return;
+            }
+
+            let gen_bin = setup.gen_bin.clone();
+            let solve_bin = setup.solve_bin.clone();
+
+            if let Some(path) = &config.config_file {
+                if let Err(err) = Config::save_backend(path, &gen_bin, &solve_bin) {
+                    app.error = Some(format!("Failed to save the setup.\n\n{err}"));
+                    return;
+                }
+            }
+
+            if let Err(err) = install_process_backend(app, config, gen_bin, solve_bin) {
+                app.error = Some(format!("Failed to set up the backend.\n\n{err}"));
+                return;
+            }
+
+            app.setup = None;
+            app.current_screen = CurrentScreen::Main;
+        }
+        KeyCode::Char(c) if !ctrl => setup.edit(|bin| bin.push(c)),
+        _ => {}
+    }
+}
+
+fn open_key(app: &mut App, key: KeyEvent) {
+    let browser = app.browser.as_mut().unwrap();
+
+    match key.code {
+        KeyCode::Esc => {
+            app.browser = None;
+            app.current_screen = CurrentScreen::Main;
+        }
+        KeyCode::Down => browser.next(),
+        KeyCode::Up => browser.previous(),
+        KeyCode::Backspace | KeyCode::Left => {
+            if let Err(err) = browser.parent() {
+                app.error = Some(format!("Failed to open the directory.\n\n{err}"));
+            }
+        }
+        KeyCode::Enter => match browser.select() {
+            Ok(Some(path)) => {
+                app.browser = None;
+                app.current_screen = CurrentScreen::Main;
+
+                if let Err(err) = app.open_file(&path) {
+                    app.error = Some(format!("Failed to open {}.\n\n{err}", path.display()));
+                }
+            }
+            Ok(None) => {}
+            Err(err) => app.error = Some(format!("Failed to open the directory.\n\n{err}")),
+        },
+        _ => {}
+    }
+}
+
+fn save_key(app: &mut App, key: KeyEvent) {
+    let save = app.save.as_mut().unwrap();
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+    match key.code {
+        KeyCode::Esc => {
+            app.save = None;
+            app.current_screen = CurrentScreen::Main;
+        }
+        KeyCode::Tab => save.switch_kind(),
+        KeyCode::Backspace => save.edit(|path| {
+            path.pop();
+        }),
+        KeyCode::Enter => {
+            let Some(path) = save.target() else {
+                return;
+            };
+            let kind = save.kind;
+
+            match app.save_file(kind, &path) {
+                Ok(()) => {
+                    app.save.as_mut().unwrap().status = Some(format!("Saved {}", path.display()))
+                }
+                Err(err) => {
+                    app.error = Some(format!("Failed to save {}.\n\n{err}", path.display()))
+                }
+            }
+        }
+        KeyCode::Char(c) if !ctrl => save.edit(|path| path.push(c)),
+        _ => {}
+    }
+}
+
+// Replaces the backend that calls external tools. With neither binary set only the
+// built-in backend is left.
+fn install_process_backend(
+    app: &mut App,
+    config: &Config,
+    gen_bin: String,
+    solve_bin: String,
+) -> io::Result<()> {
+    app.backends.truncate(1);
+    app.set_backend(0);
+
+    if gen_bin.is_empty() && solve_bin.is_empty() {
+        return Ok(());
+    }
+
+    let files = WorkDir::create(
+        config.steps_path.clone(),
+        config.maze_path.clone(),
+        config.keep_files.unwrap_or(false),
+    )?;
+
+    if files.keep() {
+        app.kept_dirs.push(files.dir().to_path_buf());
+    }
+
+    app.backends.push(Arc::new(ProcessBackend {
+        gen_bin,
+        solve_bin,
+        files,
+    }));
+    app.set_backend(app.backends.len() - 1);
+
+    Ok(())
+}
+
+fn finish_job(app: &mut App, result: Result<Maze, BackendError>) {
+    let job = app.job.take().unwrap();
+
+    // a cancelled or failed run leaves the previous maze alone
+    if job.is_cancelled() {
+        app.abandon_run();
+        return;
+    }
+
+    let maze = match result {
+        Ok(maze) => maze,
+        Err(err) => {
+            app.abandon_run();
+            app.error = Some(format!("{} failed.\n\n{err}", job.label));
+            return;
+        }
+    };
+
+    app.finish_run(maze);
+
+    match job.kind.clone() {
+        JobKind::Generate { seed, generator } => {
+            app.has_generated = true;
+            app.last_seed = seed;
+            app.last_generator = Some(generator);
+            app.last_solver = None;
+            app.generated_maze = app.maze_steps.last();
+        }
+        JobKind::Solve { solver } => app.last_solver = Some(solver),
+    }
+}
+
 fn enter_value(val: usize, current_val: usize, max_val: usize) -> usize {
     let value = current_val * 10 + val;
 
@@ -565,6 +1000,17 @@ fn enter_value(val: usize, current_val: usize, max_val: usize) -> usize {
     }
 }
 
+// Like `enter_value`, but a digit that would overflow the seed is ignored.
+fn enter_seed(val: u64, current_val: Option<u64>) -> Option<u64> {
+    match current_val {
+        None => Some(val),
+        Some(seed) => seed
+            .checked_mul(10)
+            .and_then(|seed| seed.checked_add(val))
+            .or(current_val),
+    }
+}
+
 fn update_ratio(val: usize, app: &mut App) {
     if let GenAlgorithms::GrowingTree(method) = app.gen_algorithm.clone() {
         app.tmp = match method {
@@ -0,0 +1,669 @@
+use std::{fmt, ops::BitOr};
+
+/// The walls around a cell.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Walls(u8);
+
+impl Walls {
+    pub const NONE: Walls = Walls(0);
+    pub const UP: Walls = Walls(1);
+    pub const DOWN: Walls = Walls(1 << 1);
+    pub const LEFT: Walls = Walls(1 << 2);
+    pub const RIGHT: Walls = Walls(1 << 3);
+    pub const ALL: Walls = Walls(0b1111);
+
+    pub fn contains(self, walls: Walls) -> bool {
+        self.0 & walls.0 == walls.0
+    }
+
+    pub fn insert(&mut self, walls: Walls) {
+        self.0 |= walls.0;
+    }
+
+    pub fn remove(&mut self, walls: Walls) {
+        self.0 &= !walls.0;
+    }
+}
+
+impl BitOr for Walls {
+    type Output = Walls;
+
+    fn bitor(self, rhs: Walls) -> Walls {
+        Walls(self.0 | rhs.0)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn wall(self) -> Walls {
+        match self {
+            Direction::Up => Walls::UP,
+            Direction::Down => Walls::DOWN,
+            Direction::Left => Walls::LEFT,
+            Direction::Right => Walls::RIGHT,
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// What a generator or solver has written into a cell.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Marker {
+    #[default]
+    Empty,
+    /// Waiting to be visited (`Q`).
+    Queued,
+    /// Already visited (`:`).
+    Observed,
+    /// On the branch being explored (`.`).
+    Path,
+    /// On the finished route (`*`).
+    Route,
+    /// On the finished route but never visited (`q`).
+    QueuedRoute,
+    Start,
+    Stop,
+    /// The start once a route reaches it (`s`).
+    RouteStart,
+    /// The stop once a route reaches it (`x`).
+    RouteStop,
+}
+
+impl Marker {
+    /// Reads a cell character.
+    pub fn from_char(c: char) -> Option<Marker> {
+        let marker = match c {
+            ' ' => Marker::Empty,
+            'Q' => Marker::Queued,
+            ':' => Marker::Observed,
+            '.' => Marker::Path,
+            '*' => Marker::Route,
+            'q' => Marker::QueuedRoute,
+            'S' => Marker::Start,
+            'X' => Marker::Stop,
+            's' => Marker::RouteStart,
+            'x' => Marker::RouteStop,
+            _ => return None,
+        };
+
+        Some(marker)
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Marker::Empty => ' ',
+            Marker::Queued => 'Q',
+            Marker::Observed => ':',
+            Marker::Path => '.',
+            Marker::Route => '*',
+            Marker::QueuedRoute => 'q',
+            Marker::Start => 'S',
+            Marker::Stop => 'X',
+            Marker::RouteStart => 's',
+            Marker::RouteStop => 'x',
+        }
+    }
+
+    pub fn is_queued(self) -> bool {
+        matches!(self, Marker::Queued | Marker::QueuedRoute)
+    }
+
+    pub fn is_path(self) -> bool {
+        matches!(self, Marker::Path) || self.is_route()
+    }
+
+    pub fn is_route(self) -> bool {
+        matches!(
+            self,
+            Marker::Route | Marker::QueuedRoute | Marker::RouteStart | Marker::RouteStop
+        )
+    }
+
+    pub fn is_start(self) -> bool {
+        matches!(self, Marker::Start | Marker::RouteStart)
+    }
+
+    pub fn is_stop(self) -> bool {
+        matches!(self, Marker::Stop | Marker::RouteStop)
+    }
+}
+
+/// Why some text isn't a maze grid. Lines and columns count from 1.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub reason: String,
+}
+
+impl ParseError {
+    fn new(line: usize, column: usize, reason: String) -> ParseError {
+        ParseError {
+            line,
+            column,
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Cell {
+    pub walls: Walls,
+    pub marker: Marker,
+}
+
+/// A grid of cells, parsed once from the `#`-grid text format.
+///
+/// Cells are addressed by `y * width + x`. A wall between two cells is stored on both of
+/// them.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Maze {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Maze {
+    /// A maze with every wall in place.
+    pub fn closed(width: usize, height: usize) -> Maze {
+        let cell = Cell {
+            walls: Walls::ALL,
+            marker: Marker::Empty,
+        };
+
+        Maze {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    /// A maze with only the outer walls.
+    pub fn open(width: usize, height: usize) -> Maze {
+        let mut maze = Maze::closed(width, height);
+
+        for cell in 0..maze.len() {
+            for direction in Direction::ALL {
+                if maze.neighbour(cell, direction).is_some() {
+                    maze.cells[cell].walls.remove(direction.wall());
+                }
+            }
+        }
+
+        maze
+    }
+
+    /// Reads a maze grid: an odd number of rows, all the same odd width, closed in by
+    /// `#`. Between two cells is either a `#` wall or a space, and every cell holds a
+    /// [`Marker`] character. Lines starting with `;` are comments, such as the header of
+    /// a saved file.
+    pub fn parse(text: &str) -> Result<Maze, ParseError> {
+        // blank and comment lines are skipped, but errors still point at the right line
+        let rows: Vec<(usize, &[u8])> = text
+            .lines()
+            .enumerate()
+            .filter(|(_, row)| !row.is_empty() && !row.starts_with(';'))
+            .map(|(i, row)| (i + 1, row.as_bytes()))
+            .collect();
+
+        let Some(&(first_line, first)) = rows.first() else {
+            return Err(ParseError::new(1, 1, "the maze is empty".to_string()));
+        };
+
+        for &(line, row) in &rows {
+            if let Some(column) = row.iter().position(|c| !c.is_ascii_graphic() && *c != b' ') {
+                // point at the whole character, even when it isn't ASCII
+                let row = String::from_utf8_lossy(row);
+                let c = row[column..].chars().next().unwrap_or('?');
+
+                return Err(ParseError::new(
+                    line,
+                    column + 1,
+                    format!("unexpected character {c:?}"),
+                ));
+            }
+        }
+
+        let width = first.len();
+
+        if width < 3 || width.is_multiple_of(2) {
+            return Err(ParseError::new(
+                first_line,
+                width,
+                format!("the first row is {width} wide, a maze needs an odd width of at least 3"),
+            ));
+        }
+
+        for &(line, row) in &rows {
+            if row.len() != width {
+                return Err(ParseError::new(
+                    line,
+                    row.len().min(width) + 1,
+                    format!("the row is {} wide, expected {width}", row.len()),
+                ));
+            }
+        }
+
+        let (last_line, last) = rows[rows.len() - 1];
+
+        if rows.len() < 3 || rows.len().is_multiple_of(2) {
+            return Err(ParseError::new(
+                last_line + 1,
+                1,
+                format!(
+                    "the maze is {} rows tall, it needs an odd number of at least 3",
+                    rows.len()
+                ),
+            ));
+        }
+
+        for (line, row) in [(first_line, first), (last_line, last)] {
+            if let Some(column) = row.iter().position(|&c| c != b'#') {
+                return Err(ParseError::new(
+                    line,
+                    column + 1,
+                    "the outer wall is open".to_string(),
+                ));
+            }
+        }
+
+        for &(line, row) in &rows {
+            if row[0] != b'#' {
+                return Err(ParseError::new(
+                    line,
+                    1,
+                    "the outer wall is open".to_string(),
+                ));
+            }
+            if row[width - 1] != b'#' {
+                return Err(ParseError::new(
+                    line,
+                    width,
+                    "the outer wall is open".to_string(),
+                ));
+            }
+        }
+
+        let width = width / 2;
+        let height = rows.len() / 2;
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (y, grid_row) in rows.iter().enumerate() {
+            let (line, row) = *grid_row;
+
+            for (x, &c) in row.iter().enumerate() {
+                // odd rows and columns hold cells, the rest walls and corners
+                let reason = match (y % 2, x % 2) {
+                    (1, 1) if Marker::from_char(c as char).is_none() => {
+                        format!("unknown cell character {:?}", c as char)
+                    }
+                    (0, 0) | (1, 1) => continue,
+                    _ if c == b'#' || c == b' ' => continue,
+                    _ => format!("expected a wall '#' or a passage ' ', got {:?}", c as char),
+                };
+
+                return Err(ParseError::new(line, x + 1, reason));
+            }
+        }
+
+        for y in 0..height {
+            let (above, row, below) = (rows[2 * y].1, rows[2 * y + 1].1, rows[2 * y + 2].1);
+
+            for x in 0..width {
+                let gx = 2 * x + 1;
+                let mut walls = Walls::NONE;
+
+                for (c, wall) in [
+                    (above[gx], Walls::UP),
+                    (below[gx], Walls::DOWN),
+                    (row[gx - 1], Walls::LEFT),
+                    (row[gx + 1], Walls::RIGHT),
+                ] {
+                    if c == b'#' {
+                        walls.insert(wall);
+                    }
+                }
+
+                cells.push(Cell {
+                    walls,
+                    // checked above
+                    marker: Marker::from_char(row[gx] as char).unwrap_or_default(),
+                });
+            }
+        }
+
+        Ok(Maze {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn x(&self, cell: usize) -> usize {
+        cell % self.width
+    }
+
+    pub fn y(&self, cell: usize) -> usize {
+        cell / self.width
+    }
+
+    pub fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    pub fn cell(&self, cell: usize) -> Cell {
+        self.cells[cell]
+    }
+
+    /// Replaces a cell as is. The walls it shares with its neighbours are not updated.
+    pub fn set_cell(&mut self, cell: usize, value: Cell) {
+        self.cells[cell] = value;
+    }
+
+    pub fn marker(&self, cell: usize) -> Marker {
+        self.cells[cell].marker
+    }
+
+    pub fn set_marker(&mut self, cell: usize, marker: Marker) {
+        self.cells[cell].marker = marker;
+    }
+
+    /// Empties every cell, keeping the walls.
+    pub fn clear_markers(&mut self) {
+        for cell in &mut self.cells {
+            cell.marker = Marker::Empty;
+        }
+    }
+
+    /// The first cell holding a marker that matches.
+    pub fn find(&self, matches: impl Fn(Marker) -> bool) -> Option<usize> {
+        self.cells.iter().position(|cell| matches(cell.marker))
+    }
+
+    pub fn has_wall(&self, cell: usize, direction: Direction) -> bool {
+        self.cells[cell].walls.contains(direction.wall())
+    }
+
+    /// The cell next to `cell`, if it isn't on that edge.
+    pub fn neighbour(&self, cell: usize, direction: Direction) -> Option<usize> {
+        let (x, y) = (self.x(cell), self.y(cell));
+
+        match direction {
+            Direction::Up if y > 0 => Some(cell - self.width),
+            Direction::Down if y + 1 < self.height => Some(cell + self.width),
+            Direction::Left if x > 0 => Some(cell - 1),
+            Direction::Right if x + 1 < self.width => Some(cell + 1),
+            _ => None,
+        }
+    }
+
+    /// Every cell next to `cell`, walls or not.
+    pub fn neighbours(&self, cell: usize) -> Vec<usize> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(|direction| self.neighbour(cell, direction))
+            .collect()
+    }
+
+    /// The cells reachable from `cell` in one move.
+    pub fn open_neighbours(&self, cell: usize) -> Vec<usize> {
+        Direction::ALL
+            .into_iter()
+            .filter(|&direction| !self.has_wall(cell, direction))
+            .filter_map(|direction| self.neighbour(cell, direction))
+            .collect()
+    }
+
+    /// Removes the wall between two adjacent cells.
+    pub fn carve(&mut self, a: usize, b: usize) {
+        let direction = self.direction(a, b);
+
+        self.cells[a].walls.remove(direction.wall());
+        self.cells[b].walls.remove(direction.opposite().wall());
+    }
+
+    /// Places a wall between two adjacent cells.
+    pub fn build_wall(&mut self, a: usize, b: usize) {
+        let direction = self.direction(a, b);
+
+        self.cells[a].walls.insert(direction.wall());
+        self.cells[b].walls.insert(direction.opposite().wall());
+    }
+
+    fn direction(&self, a: usize, b: usize) -> Direction {
+        Direction::ALL
+            .into_iter()
+            .find(|&direction| self.neighbour(a, direction) == Some(b))
+            .unwrap_or_else(|| panic!("cells {a} and {b} are not adjacent"))
+    }
+}
+
+impl fmt::Display for Maze {
+    /// Writes the `#`-grid text the maze was parsed from.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let wall = |walls: Walls, wall| if walls.contains(wall) { '#' } else { ' ' };
+        let mut row = String::with_capacity(2 * self.width + 1);
+
+        for y in 0..self.height {
+            row.clear();
+            for x in 0..self.width {
+                row.push('#');
+                row.push(wall(self.cell(self.index(x, y)).walls, Walls::UP));
+            }
+            row.push('#');
+            writeln!(f, "{row}")?;
+
+            row.clear();
+            for x in 0..self.width {
+                let cell = self.cell(self.index(x, y));
+
+                if x == 0 {
+                    row.push(wall(cell.walls, Walls::LEFT));
+                }
+                row.push(cell.marker.to_char());
+                row.push(wall(cell.walls, Walls::RIGHT));
+            }
+            writeln!(f, "{row}")?;
+        }
+
+        row.clear();
+        for x in 0..self.width {
+            row.push('#');
+            row.push(wall(
+                self.cell(self.index(x, self.height - 1)).walls,
+                Walls::DOWN,
+            ));
+        }
+        row.push('#');
+
+        write!(f, "{row}")
+    }
+}
+
+#[cfg(test)]
+mod maze_tests {
+    use super::*;
+
+    #[test]
+    fn parse_closed_maze() {
+        let maze = Maze::parse(
+            "\
+#####
+# # #
+#####
+# # #
+#####",
+        )
+        .unwrap();
+
+        assert_eq!(maze.height(), 2);
+        assert_eq!(maze.width(), 2);
+        assert_eq!(maze, Maze::closed(2, 2));
+
+        for cell in maze.cells() {
+            assert_eq!(cell.walls, Walls::ALL);
+            assert_eq!(cell.marker, Marker::Empty);
+        }
+    }
+
+    #[test]
+    fn parse_markers_and_walls() {
+        let text = "\
+#######
+#Q : x#
+# #####
+#. *  #
+##### #
+#s    #
+#######";
+        let maze = Maze::parse(text).unwrap();
+
+        assert_eq!(maze.height(), 3);
+        assert_eq!(maze.width(), 3);
+
+        let markers: Vec<Marker> = maze.cells().iter().map(|cell| cell.marker).collect();
+        assert_eq!(
+            markers,
+            [
+                Marker::Queued,
+                Marker::Observed,
+                Marker::RouteStop,
+                Marker::Path,
+                Marker::Route,
+                Marker::Empty,
+                Marker::RouteStart,
+                Marker::Empty,
+                Marker::Empty,
+            ]
+        );
+
+        assert_eq!(maze.cell(0).walls, Walls::UP | Walls::LEFT);
+        assert_eq!(maze.cell(4).walls, Walls::UP | Walls::DOWN);
+        assert_eq!(maze.cell(8).walls, Walls::DOWN | Walls::RIGHT);
+        assert_eq!(maze.open_neighbours(3), [0, 4]);
+        assert_eq!(maze.open_neighbours(5), [8, 4]);
+
+        assert!(maze.marker(2).is_stop() && maze.marker(2).is_route());
+        assert!(maze.marker(0).is_queued() && !maze.marker(0).is_path());
+        assert!(maze.marker(3).is_path() && !maze.marker(3).is_route());
+
+        assert_eq!(maze.to_string(), text, "Display should round trip");
+    }
+
+    #[test]
+    fn parse_rejects_bad_grids() {
+        let error = |text| Maze::parse(text).unwrap_err().to_string();
+
+        assert!(Maze::parse("###\n# #\n###").is_ok());
+        assert_eq!(error(""), "line 1, column 1: the maze is empty");
+        assert_eq!(
+            error("###\n# #\n####"),
+            "line 3, column 4: the row is 4 wide, expected 3"
+        );
+        assert_eq!(
+            error("###\n# #"),
+            "line 3, column 1: the maze is 2 rows tall, it needs an odd number of at least 3"
+        );
+        assert_eq!(
+            error("####\n#  #\n####"),
+            "line 1, column 4: the first row is 4 wide, a maze needs an odd width of at least 3"
+        );
+        assert_eq!(
+            error("###\n#\u{fffd}#\n###"),
+            "line 2, column 2: unexpected character '\u{fffd}'"
+        );
+        assert_eq!(
+            error("#####\n#Z  #\n#####"),
+            "line 2, column 2: unknown cell character 'Z'"
+        );
+        assert_eq!(
+            error("#####\n# . #\n#####"),
+            "line 2, column 3: expected a wall '#' or a passage ' ', got '.'"
+        );
+        assert_eq!(
+            error("#####\n#    \n#####"),
+            "line 2, column 5: the outer wall is open"
+        );
+        assert_eq!(
+            error("\n\n#####\n#   #\n## ##"),
+            "line 5, column 3: the outer wall is open"
+        );
+        assert_eq!(
+            error("; size: 1x1\n###\n# #\n####"),
+            "line 4, column 4: the row is 4 wide, expected 3"
+        );
+        assert!(Maze::parse("; seed: 7\n###\n# #\n###").is_ok());
+    }
+
+    #[test]
+    fn carve_and_build_walls() {
+        let mut maze = Maze::closed(3, 2);
+
+        maze.carve(0, 1);
+        maze.carve(1, 4);
+
+        assert_eq!(maze.open_neighbours(1), [4, 0]);
+        assert!(!maze.has_wall(4, Direction::Up));
+        assert!(maze.has_wall(4, Direction::Left));
+
+        maze.build_wall(4, 1);
+
+        assert_eq!(maze.open_neighbours(1), [0]);
+        assert_eq!(maze.neighbours(4), [1, 3, 5]);
+        assert_eq!(Maze::open(3, 2).open_neighbours(4), [1, 3, 5]);
+    }
+}
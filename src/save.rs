@@ -0,0 +1,267 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{maze::Maze, steps::Steps};
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum SaveKind {
+    Maze,
+    Steps,
+    Frame,
+    Cast,
+    Gif,
+    Svg,
+}
+
+/// How a saved maze came to be, written as a header of `;` comment lines above the first
+/// grid. [`Maze::parse`] skips comments, so the file still opens as a maze or step file.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Metadata {
+    pub generator: Option<String>,
+    pub seed: Option<u64>,
+    pub solver: Option<String>,
+}
+
+/// State of the screen for saving the maze, its steps or the frame on screen.
+pub struct Save {
+    pub kind: SaveKind,
+    pub path: String,
+    // set once the user has been warned that `path` exists
+    overwrite: bool,
+    pub status: Option<String>,
+}
+
+impl SaveKind {
+    const ALL: [SaveKind; 6] = [
+        SaveKind::Maze,
+        SaveKind::Steps,
+        SaveKind::Frame,
+        SaveKind::Cast,
+        SaveKind::Gif,
+        SaveKind::Svg,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            SaveKind::Maze => "Maze",
+            SaveKind::Steps => "Steps",
+            SaveKind::Frame => "Current frame",
+            SaveKind::Cast => "Playback as an asciinema recording",
+            SaveKind::Gif => "Playback as an animated GIF",
+            SaveKind::Svg => "Current frame as an SVG image",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SaveKind::Steps => "steps",
+            SaveKind::Maze | SaveKind::Frame => "mz",
+            SaveKind::Cast => "cast",
+            SaveKind::Gif => "gif",
+            SaveKind::Svg => "svg",
+        }
+    }
+}
+
+impl Metadata {
+    fn write_header(&self, out: &mut impl Write, maze: &Maze, steps: usize) -> io::Result<()> {
+        writeln!(out, "; MazeViewerTUI")?;
+
+        if let Some(generator) = &self.generator {
+            writeln!(out, "; generator: {generator}")?;
+        }
+
+        if let Some(seed) = self.seed {
+            writeln!(out, "; seed: {seed}")?;
+        }
+
+        if let Some(solver) = &self.solver {
+            writeln!(out, "; solver: {solver}")?;
+        }
+
+        writeln!(out, "; size: {}x{}", maze.width(), maze.height())?;
+        writeln!(out, "; steps: {steps}")
+    }
+}
+
+impl Save {
+    pub fn new() -> Save {
+        Save {
+            kind: SaveKind::Maze,
+            path: "maze.mz".to_string(),
+            overwrite: false,
+            status: None,
+        }
+    }
+
+    /// Cycles what is saved, keeping the file extension in step.
+    pub fn switch_kind(&mut self) {
+        let next = SaveKind::ALL
+            .iter()
+            .position(|kind| *kind == self.kind)
+            .unwrap()
+            + 1;
+        self.kind = SaveKind::ALL[next % SaveKind::ALL.len()];
+
+        let path = Path::new(&self.path);
+
+        if path
+            .extension()
+            .is_some_and(|ext| SaveKind::ALL.iter().any(|kind| ext == kind.extension()))
+        {
+            self.path = path
+                .with_extension(self.kind.extension())
+                .to_string_lossy()
+                .into_owned();
+        }
+
+        self.overwrite = false;
+        self.status = None;
+    }
+
+    pub fn edit(&mut self, edit: impl FnOnce(&mut String)) {
+        edit(&mut self.path);
+        self.overwrite = false;
+        self.status = None;
+    }
+
+    /// The path to write to, or `None` after warning that it would replace a file. Asking
+    /// again for the same path allows the overwrite.
+    pub fn target(&mut self) -> Option<PathBuf> {
+        let path = PathBuf::from(&self.path);
+
+        if path.exists() && !self.overwrite {
+            self.overwrite = true;
+            self.status = Some(format!(
+                "{} already exists, press Enter again to replace it",
+                path.display()
+            ));
+            return None;
+        }
+
+        Some(path)
+    }
+}
+
+/// Writes a single maze with a header.
+pub fn save_maze(path: &Path, maze: &Maze, metadata: &Metadata) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+
+    metadata.write_header(&mut out, maze, 1)?;
+    writeln!(out, "{maze}")?;
+
+    out.flush()
+}
+
+/// Writes every step, separated by blank lines, with a header.
+pub fn save_steps(path: &Path, steps: &Steps, metadata: &Metadata) -> io::Result<()> {
+    let Some(last) = steps.last() else {
+        return Err(io::Error::other("there are no steps to save"));
+    };
+
+    let mut out = BufWriter::new(File::create(path)?);
+
+    metadata.write_header(&mut out, &last, steps.len())?;
+
+    for i in 0..steps.len() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+
+        writeln!(out, "{}", steps.get(i)?)?;
+    }
+
+    out.flush()
+}
+
+#[cfg(test)]
+mod save_tests {
+    use std::{env, fs, process, thread};
+
+    use super::*;
+    use crate::{
+        maze::Marker,
+        steps::{StepFile, StepParser},
+    };
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("save_test-{}-{name}", process::id()))
+    }
+
+    fn metadata() -> Metadata {
+        Metadata {
+            generator: Some("Kruskal".to_string()),
+            seed: Some(42),
+            solver: None,
+        }
+    }
+
+    #[test]
+    fn saved_steps_open_again() {
+        let path = temp_path("run.steps");
+        let mut steps = Steps::default();
+        let mut maze = Maze::closed(2, 2);
+
+        steps.push(maze.clone());
+        maze.carve(0, 1);
+        maze.set_marker(1, Marker::Queued);
+        steps.push(maze.clone());
+
+        save_steps(&path, &steps, &metadata()).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with(
+            "; MazeViewerTUI\n; generator: Kruskal\n; seed: 42\n; size: 2x2\n; steps: 2\n#####\n"
+        ));
+
+        let mut parser = StepParser::new();
+        let mut frames = parser.feed(text.as_bytes());
+        frames.extend(parser.finish());
+        assert_eq!(frames.len(), 2, "The header doesn't count as a step");
+
+        let file = StepFile::open(&path).unwrap();
+        while !file.is_indexed() {
+            thread::yield_now();
+        }
+
+        assert_eq!(file.len(), 2);
+        assert_eq!(file.get(0).unwrap(), Maze::closed(2, 2));
+        assert_eq!(file.get(1).unwrap(), maze);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn saved_maze_parses() {
+        let path = temp_path("maze.mz");
+        let maze = Maze::open(3, 2);
+
+        save_maze(&path, &maze, &Metadata::default()).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(Maze::parse(&text).unwrap(), maze);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn asks_before_overwriting() {
+        let path = temp_path("exists.mz");
+        fs::write(&path, "").unwrap();
+
+        let mut save = Save::new();
+        save.edit(|p| *p = path.to_string_lossy().into_owned());
+
+        assert_eq!(save.target(), None);
+        assert!(save.status.is_some());
+        assert_eq!(save.target(), Some(path.clone()));
+
+        save.switch_kind();
+        assert!(save.path.ends_with("exists.steps"));
+
+        let _ = fs::remove_file(path);
+    }
+}
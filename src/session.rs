@@ -0,0 +1,308 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    app::{App, GenAlgorithms},
+    config::Config,
+    maze::Maze,
+    save,
+    steps::Steps,
+};
+
+/// What was going on when the viewer was last closed: the settings, the last run and
+/// where its playback was.
+///
+/// The settings use the `defaults` layout of the config file, and `generators` holds the
+/// choice made for every entry of the generator list. Steps collected in memory are
+/// written next to the session file unless there are too many, while a step file that
+/// was opened is only referred to by its path.
+#[derive(Debug, PartialEq)]
+pub struct Session {
+    pub settings: Config,
+    pub generators: Vec<String>,
+    pub generator: Option<String>,
+    pub seed: Option<u64>,
+    pub solver: Option<String>,
+    pub maze: Option<Maze>,
+    pub steps: Option<PathBuf>,
+    pub step: usize,
+}
+
+// Steps from a run that would take more text than this aren't saved, so quitting stays
+// quick. The maze they made is still restored.
+const MAX_STEPS_TEXT: usize = 64 * 1024 * 1024;
+
+/// `$XDG_STATE_HOME/MazeViewerTUI/session.json`, falling back to `~/.local/state` and to
+/// `%LOCALAPPDATA%` on Windows.
+pub fn default_path() -> Option<PathBuf> {
+    let base = env::var_os("XDG_STATE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))?;
+
+    Some(base.join("MazeViewerTUI").join("session.json"))
+}
+
+impl Session {
+    /// Saves the state of `app` to `path`. Steps from a run go to the same path with a
+    /// `.steps` extension.
+    pub fn save(app: &App, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let steps_path = path.with_extension("steps");
+        let maze = &app.maze;
+        let text = app.maze_steps.len() * (2 * maze.width() + 2) * (2 * maze.height() + 2);
+
+        let steps = match &app.maze_steps {
+            Steps::File(file) => Some(file.path().to_path_buf()),
+            Steps::Memory(_) if app.maze_steps.is_empty() || !app.has_generated => None,
+            Steps::Memory(_) if text > MAX_STEPS_TEXT => None,
+            Steps::Memory(_) => {
+                save::save_steps(&steps_path, &app.maze_steps, &app.metadata())?;
+                Some(steps_path.clone())
+            }
+        };
+
+        // don't leave the steps of an older session lying around
+        if steps.as_ref() != Some(&steps_path) && steps_path.exists() {
+            fs::remove_file(&steps_path)?;
+        }
+
+        let value = json!({
+            "defaults": {
+                "generator": app.gen_algorithm.get_name(),
+                "solver": app.solve_algorithm.get_name(),
+                "width": app.get_width(),
+                "height": app.get_height(),
+                "speed": app.get_speed(),
+                "ratio": app.get_ratio(),
+                "seed": app.seed,
+            },
+            "generators": app
+                .gen_algo_lookup
+                .iter()
+                .map(GenAlgorithms::to_string)
+                .collect::<Vec<String>>(),
+            "run": {
+                "generator": app.last_generator,
+                "seed": app.last_seed,
+                "solver": app.last_solver,
+                "maze": app.generated_maze.as_ref().map(Maze::to_string),
+                "steps": steps,
+                "step": app.get_step_val(),
+            },
+        });
+
+        let text = serde_json::to_string_pretty(&value).unwrap();
+
+        fs::write(path, text + "\n")
+    }
+
+    pub fn load(path: &Path) -> io::Result<Session> {
+        let invalid = |err| io::Error::new(io::ErrorKind::InvalidData, err);
+
+        let value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let settings = Config::from_json(&json!({ "defaults": value["defaults"] }))
+            .map_err(|err| invalid(err.to_string()))?;
+
+        let run = &value["run"];
+        let maze = run["maze"]
+            .as_str()
+            .map(Maze::parse)
+            .transpose()
+            .map_err(|err| invalid(err.to_string()))?;
+
+        let generators = value["generators"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|name| name.as_str().map(str::to_string))
+            .collect();
+
+        Ok(Session {
+            settings,
+            generators,
+            generator: run["generator"].as_str().map(str::to_string),
+            seed: run["seed"].as_u64(),
+            solver: run["solver"].as_str().map(str::to_string),
+            maze,
+            steps: run["steps"].as_str().map(PathBuf::from),
+            step: run["step"].as_u64().unwrap_or(0) as usize,
+        })
+    }
+
+    /// Puts the session back into `app`. The steps are read in the background, and
+    /// playback picks up where it was once they are.
+    pub fn restore(self, app: &mut App) -> io::Result<()> {
+        self.settings.apply(app);
+
+        let ratio = app.get_ratio();
+
+        for algorithm in self
+            .generators
+            .iter()
+            .filter_map(|name| GenAlgorithms::from_name(name, ratio))
+        {
+            let same = |el: &GenAlgorithms| {
+                std::mem::discriminant(el) == std::mem::discriminant(&algorithm)
+            };
+
+            if let Some(index) = app.gen_algo_lookup.iter().position(same) {
+                app.gen_algo_lookup[index] = algorithm;
+            }
+        }
+
+        if let Some(width) = self.settings.width {
+            app.set_width(width);
+        }
+
+        if let Some(height) = self.settings.height {
+            app.set_height(height);
+        }
+
+        app.clear_maze();
+
+        if let Some(path) = &self.steps {
+            app.open_file_at(path, self.step)?;
+        } else if let Some(maze) = &self.maze {
+            app.maze = maze.clone();
+            app.has_generated = true;
+        }
+
+        if self.maze.is_some() {
+            app.generated_maze = self.maze;
+        }
+
+        app.last_generator = self.generator;
+        app.last_seed = self.seed;
+        app.last_solver = self.solver;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod session_tests {
+    use std::{env, process};
+
+    use super::*;
+    use crate::{
+        app::{BiasMethods, SolveAlgorithms, TreeSubAlgorithm},
+        maze::Marker,
+    };
+
+    #[test]
+    fn save_and_load() {
+        let dir = env::temp_dir().join(format!("session_test-{}", process::id()));
+        let path = dir.join("session.json");
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut app = App::new();
+        let mut maze = Maze::open(2, 2);
+
+        app.set_speed(30);
+        app.solve_algorithm = SolveAlgorithms::AStar;
+        app.seed = Some(9);
+        app.maze_steps.push(Maze::closed(2, 2));
+        app.maze_steps.push(maze.clone());
+        maze.set_marker(0, Marker::Start);
+        app.generated_maze = Some(maze.clone());
+        app.has_generated = true;
+        app.last_generator = Some("Prim".to_string());
+        app.last_seed = Some(4);
+        app.set_step_val(1);
+
+        Session::save(&app, &path).unwrap();
+
+        let session = Session::load(&path).unwrap();
+
+        assert_eq!(session.settings.speed, Some(30));
+        assert_eq!(session.settings.solver.as_deref(), Some("A-Star"));
+        assert_eq!(session.settings.seed, Some(9));
+        assert_eq!(session.generator.as_deref(), Some("Prim"));
+        assert_eq!(session.seed, Some(4));
+        assert_eq!(session.solver, None);
+        assert_eq!(session.maze, Some(maze));
+        assert_eq!(session.steps, Some(dir.join("session.steps")));
+        assert_eq!(session.step, 1);
+
+        app.clear_maze();
+        Session::save(&app, &path).unwrap();
+
+        assert!(
+            !dir.join("session.steps").exists(),
+            "Old steps should be removed"
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn every_generator_choice_is_kept() {
+        let dir = env::temp_dir().join(format!("session_test-gens-{}", process::id()));
+        let path = dir.join("session.json");
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut app = App::new();
+        let chosen = [
+            GenAlgorithms::GrowingTree(TreeSubAlgorithm::Oldest),
+            GenAlgorithms::BinaryTree(BiasMethods::SouthEast),
+        ];
+
+        for algorithm in &chosen {
+            let index = app
+                .gen_algo_lookup
+                .iter()
+                .position(|el| std::mem::discriminant(el) == std::mem::discriminant(algorithm))
+                .unwrap();
+            app.gen_algo_lookup[index] = algorithm.clone();
+        }
+
+        Session::save(&app, &path).unwrap();
+
+        let mut restored = App::new();
+        let session = Session::load(&path).unwrap();
+        session.restore(&mut restored).unwrap();
+
+        for algorithm in &chosen {
+            assert!(restored.gen_algo_lookup.contains(algorithm));
+        }
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn large_runs_keep_only_the_maze() {
+        let dir = env::temp_dir().join(format!("session_test-large-{}", process::id()));
+        let path = dir.join("session.json");
+        let _ = fs::remove_dir_all(&dir);
+
+        let mut app = App::new();
+        let maze = Maze::open(1000, 1000);
+
+        // a step of a 1000x1000 maze is about 4MB of text
+        for _ in 0..20 {
+            app.maze_steps.push(maze.clone());
+        }
+        app.maze = maze.clone();
+        app.generated_maze = Some(maze.clone());
+        app.has_generated = true;
+
+        Session::save(&app, &path).unwrap();
+
+        let session = Session::load(&path).unwrap();
+
+        assert_eq!(session.steps, None);
+        assert_eq!(session.maze, Some(maze));
+        assert!(!dir.join("session.steps").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    app::{GenAlgorithms, SolveAlgorithms},
+    backend::{BackendError, BuiltinBackend, Cancel, MazeBackend, ProcessBackend, StepSink},
+    browser::FileBrowser,
+    workdir::WorkDir,
+};
+
+// How long a tool gets to handle the probe maze before it's given up on.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum SetupField {
+    Generator,
+    Solver,
+}
+
+pub enum ProbeStatus {
+    Untested,
+    Passed,
+    Failed(String),
+}
+
+/// State of the screen for picking the generator and solver binaries. An empty path
+/// means the built-in implementation is used.
+pub struct Setup {
+    pub field: SetupField,
+    pub gen_bin: String,
+    pub solve_bin: String,
+    pub gen_status: ProbeStatus,
+    pub solve_status: ProbeStatus,
+    pub browser: Option<FileBrowser>,
+}
+
+impl Setup {
+    pub fn new(gen_bin: String, solve_bin: String) -> Setup {
+        Setup {
+            field: SetupField::Generator,
+            gen_bin,
+            solve_bin,
+            gen_status: ProbeStatus::Untested,
+            solve_status: ProbeStatus::Untested,
+            browser: None,
+        }
+    }
+
+    pub fn switch_field(&mut self) {
+        self.field = match self.field {
+            SetupField::Generator => SetupField::Solver,
+            SetupField::Solver => SetupField::Generator,
+        };
+    }
+
+    pub fn input(&self) -> &str {
+        match self.field {
+            SetupField::Generator => &self.gen_bin,
+            SetupField::Solver => &self.solve_bin,
+        }
+    }
+
+    /// Changes the path being edited. Its old probe result no longer applies.
+    pub fn edit(&mut self, edit: impl FnOnce(&mut String)) {
+        match self.field {
+            SetupField::Generator => {
+                edit(&mut self.gen_bin);
+                self.gen_status = ProbeStatus::Untested;
+            }
+            SetupField::Solver => {
+                edit(&mut self.solve_bin);
+                self.solve_status = ProbeStatus::Untested;
+            }
+        }
+    }
+
+    /// Probes the path being edited.
+    pub fn probe(&mut self) {
+        match self.field {
+            SetupField::Generator => self.gen_status = status(&self.gen_bin, probe_generator),
+            SetupField::Solver => self.solve_status = status(&self.solve_bin, probe_solver),
+        }
+    }
+
+    /// Probes whatever hasn't been yet. Returns `true` if both paths can be used.
+    pub fn probe_all(&mut self) -> bool {
+        if let ProbeStatus::Untested = self.gen_status {
+            self.gen_status = status(&self.gen_bin, probe_generator);
+        }
+
+        if let ProbeStatus::Untested = self.solve_status {
+            self.solve_status = status(&self.solve_bin, probe_solver);
+        }
+
+        matches!(
+            (&self.gen_status, &self.solve_status),
+            (ProbeStatus::Passed, ProbeStatus::Passed)
+        )
+    }
+}
+
+fn status(bin: &str, probe: fn(&str) -> Result<(), BackendError>) -> ProbeStatus {
+    // the built-in implementation always works
+    if bin.is_empty() {
+        return ProbeStatus::Passed;
+    }
+
+    match probe(bin) {
+        Ok(()) => ProbeStatus::Passed,
+        Err(BackendError::Cancelled) => ProbeStatus::Failed("Timed out".to_string()),
+        Err(err) => ProbeStatus::Failed(err.to_string()),
+    }
+}
+
+/// Checks that `bin` speaks the MazeCreator command line by generating a tiny maze.
+pub fn probe_generator(bin: &str) -> Result<(), BackendError> {
+    let backend = ProcessBackend {
+        gen_bin: bin.to_string(),
+        solve_bin: String::new(),
+        files: WorkDir::create(None, None, false)?,
+    };
+
+    with_timeout(|steps| {
+        backend
+            .generate(&GenAlgorithms::Kruskal, 2, 2, 0, steps)
+            .map(|_| ())
+    })
+}
+
+/// Checks that `bin` speaks the MazeSolver command line by solving a tiny maze.
+pub fn probe_solver(bin: &str) -> Result<(), BackendError> {
+    let backend = ProcessBackend {
+        gen_bin: String::new(),
+        solve_bin: bin.to_string(),
+        files: WorkDir::create(None, None, false)?,
+    };
+
+    with_timeout(|steps| {
+        let maze = BuiltinBackend.generate(&GenAlgorithms::Kruskal, 2, 2, 0, steps)?;
+
+        backend
+            .solve(&maze, &SolveAlgorithms::Depth, steps)
+            .map(|_| ())
+    })
+}
+
+// Runs `probe`, cancelling it once it takes longer than `PROBE_TIMEOUT`.
+fn with_timeout(
+    probe: impl FnOnce(&mut StepSink) -> Result<(), BackendError>,
+) -> Result<(), BackendError> {
+    let cancel = Cancel::default();
+    let (done, finished) = mpsc::channel::<()>();
+
+    let watchdog_cancel = cancel.clone();
+    let watchdog = thread::spawn(move || {
+        if let Err(RecvTimeoutError::Timeout) = finished.recv_timeout(PROBE_TIMEOUT) {
+            watchdog_cancel.cancel();
+        }
+    });
+
+    let mut on_step = |_| {};
+    let result = probe(&mut StepSink::new(cancel, &mut on_step));
+
+    drop(done);
+    let _ = watchdog.join();
+
+    result
+}
+
+#[cfg(test)]
+mod setup_tests {
+    use super::*;
+
+    #[test]
+    fn built_in_always_passes() {
+        let mut setup = Setup::new(String::new(), String::new());
+
+        assert!(setup.probe_all());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wrong_binary_fails() {
+        let mut setup = Setup::new("true".to_string(), String::new());
+
+        assert!(!setup.probe_all());
+        assert!(matches!(setup.gen_status, ProbeStatus::Failed(_)));
+        assert!(matches!(setup.solve_status, ProbeStatus::Passed));
+
+        setup.edit(|bin| bin.clear());
+        assert!(matches!(setup.gen_status, ProbeStatus::Untested));
+    }
+}
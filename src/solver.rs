@@ -0,0 +1,369 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use crate::{
+    app::SolveAlgorithms,
+    maze::{Marker, Maze},
+};
+
+/// Solves a maze in-process and hands every step to `on_step` using the same markers as
+/// the external solver. The last step is the solved maze.
+///
+/// The start and stop are taken from the start and stop markers (solved ones are
+/// accepted too) and default to the top left and bottom right cells. Returning `false`
+/// from `on_step` stops the search, in which case this returns `false` as well.
+pub fn solve(
+    maze: &Maze,
+    algorithm: &SolveAlgorithms,
+    on_step: &mut dyn FnMut(Maze) -> bool,
+) -> bool {
+    let mut search = Search::new(maze, on_step);
+    let mut frontier = Frontier::new(algorithm);
+
+    search.dist[search.start] = 0;
+    frontier.push(search.start, 0, search.heuristic(search.start));
+    search.marks[search.start] = Mark::Queued;
+    search.snapshot(None);
+
+    while let Some(cell) = frontier.pop() {
+        if !search.recording {
+            return false;
+        }
+
+        if search.marks[cell] == Mark::Observed {
+            continue;
+        }
+
+        search.marks[cell] = Mark::Observed;
+
+        if cell == search.stop {
+            break;
+        }
+
+        for next in search.maze.open_neighbours(cell) {
+            let dist = search.dist[cell] + 1;
+
+            if search.marks[next] == Mark::Observed {
+                continue;
+            }
+
+            // depth first always follows the newest branch
+            if *algorithm == SolveAlgorithms::Depth || dist < search.dist[next] {
+                search.dist[next] = dist;
+                search.parents[next] = Some(cell);
+                search.marks[next] = Mark::Queued;
+                frontier.push(next, dist, search.heuristic(next));
+            }
+        }
+
+        search.snapshot(Some(cell));
+    }
+
+    search.finish()
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Mark {
+    Open,
+    Queued,
+    Observed,
+}
+
+struct Search<'a> {
+    maze: Maze,
+    marks: Vec<Mark>,
+    parents: Vec<Option<usize>>,
+    dist: Vec<usize>,
+    start: usize,
+    stop: usize,
+    on_step: &'a mut dyn FnMut(Maze) -> bool,
+    recording: bool,
+}
+
+enum Frontier {
+    Stack(Vec<usize>),
+    Queue(VecDeque<usize>),
+    Heap {
+        // (priority, tie breaker, insertion order, cell)
+        heap: BinaryHeap<Reverse<(usize, usize, usize, usize)>>,
+        use_heuristic: bool,
+        // how many cells have been pushed, which orders the ones that tie
+        pushed: usize,
+    },
+}
+
+impl Frontier {
+    fn new(algorithm: &SolveAlgorithms) -> Frontier {
+        match algorithm {
+            SolveAlgorithms::Depth => Frontier::Stack(Vec::new()),
+            SolveAlgorithms::Breadth => Frontier::Queue(VecDeque::new()),
+            SolveAlgorithms::Dijkstra => Frontier::heap(false),
+            SolveAlgorithms::AStar => Frontier::heap(true),
+        }
+    }
+
+    fn heap(use_heuristic: bool) -> Frontier {
+        Frontier::Heap {
+            heap: BinaryHeap::new(),
+            use_heuristic,
+            pushed: 0,
+        }
+    }
+
+    fn push(&mut self, cell: usize, dist: usize, heuristic: usize) {
+        match self {
+            Frontier::Stack(stack) => stack.push(cell),
+            Frontier::Queue(queue) => queue.push_back(cell),
+            Frontier::Heap {
+                heap,
+                use_heuristic,
+                pushed,
+            } => {
+                let order = *pushed;
+                *pushed += 1;
+
+                let entry = if *use_heuristic {
+                    (dist + heuristic, heuristic, order, cell)
+                } else {
+                    (dist, 0, order, cell)
+                };
+                heap.push(Reverse(entry));
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        match self {
+            Frontier::Stack(stack) => stack.pop(),
+            Frontier::Queue(queue) => queue.pop_front(),
+            Frontier::Heap { heap, .. } => heap.pop().map(|Reverse((_, _, _, cell))| cell),
+        }
+    }
+}
+
+impl<'a> Search<'a> {
+    fn new(maze: &Maze, on_step: &'a mut dyn FnMut(Maze) -> bool) -> Search<'a> {
+        let len = maze.len();
+        let start = maze.find(Marker::is_start).unwrap_or(0);
+        let stop = maze.find(Marker::is_stop).unwrap_or(len.saturating_sub(1));
+
+        let mut maze = maze.clone();
+        maze.clear_markers();
+
+        Search {
+            maze,
+            marks: vec![Mark::Open; len],
+            parents: vec![None; len],
+            dist: vec![usize::MAX; len],
+            start,
+            stop,
+            on_step,
+            recording: true,
+        }
+    }
+
+    fn heuristic(&self, cell: usize) -> usize {
+        let (x, y) = (self.maze.x(cell), self.maze.y(cell));
+        let (stop_x, stop_y) = (self.maze.x(self.stop), self.maze.y(self.stop));
+
+        x.abs_diff(stop_x) + y.abs_diff(stop_y)
+    }
+
+    fn path_to(&self, cell: usize) -> Vec<usize> {
+        let mut path = vec![cell];
+        let mut current = cell;
+
+        while let Some(parent) = self.parents[current] {
+            path.push(parent);
+            current = parent;
+        }
+
+        path
+    }
+
+    // Draws the search state, with the branch leading to `current` as a path.
+    fn snapshot(&mut self, current: Option<usize>) {
+        let mut maze = self.marked_maze();
+
+        if let Some(current) = current {
+            for cell in self.path_to(current) {
+                maze.set_marker(cell, Marker::Path);
+            }
+        }
+
+        maze.set_marker(self.start, Marker::Start);
+        maze.set_marker(self.stop, Marker::Stop);
+
+        self.emit(maze);
+    }
+
+    fn finish(mut self) -> bool {
+        let mut maze = self.marked_maze();
+
+        let found = self.parents[self.stop].is_some() || self.start == self.stop;
+
+        if found {
+            for cell in self.path_to(self.stop) {
+                let marker = match self.marks[cell] {
+                    Mark::Queued => Marker::QueuedRoute,
+                    _ => Marker::Route,
+                };
+                maze.set_marker(cell, marker);
+            }
+            maze.set_marker(self.start, Marker::RouteStart);
+            maze.set_marker(self.stop, Marker::RouteStop);
+        } else {
+            maze.set_marker(self.start, Marker::Start);
+            maze.set_marker(self.stop, Marker::Stop);
+        }
+
+        self.emit(maze);
+
+        self.recording
+    }
+
+    fn emit(&mut self, maze: Maze) {
+        if self.recording {
+            self.recording = (self.on_step)(maze);
+        }
+    }
+
+    fn marked_maze(&self) -> Maze {
+        let mut maze = self.maze.clone();
+
+        for (cell, mark) in self.marks.iter().enumerate() {
+            let marker = match mark {
+                Mark::Open => Marker::Empty,
+                Mark::Queued => Marker::Queued,
+                Mark::Observed => Marker::Observed,
+            };
+            maze.set_marker(cell, marker);
+        }
+
+        maze
+    }
+}
+
+#[cfg(test)]
+mod solver_tests {
+    use super::*;
+    use crate::{app::GenAlgorithms, generator::generate};
+
+    fn steps(maze: &Maze, algorithm: &SolveAlgorithms) -> Vec<Maze> {
+        let mut steps = Vec::new();
+
+        solve(maze, algorithm, &mut |step| {
+            steps.push(step);
+            true
+        });
+
+        steps
+    }
+
+    fn maze(algorithm: &GenAlgorithms, size: usize, seed: u64) -> Maze {
+        let mut maze = Maze::default();
+
+        generate(algorithm, size, size, seed, &mut |step| {
+            maze = step;
+            true
+        });
+
+        maze
+    }
+
+    const ALGORITHMS: [SolveAlgorithms; 4] = [
+        SolveAlgorithms::Depth,
+        SolveAlgorithms::Breadth,
+        SolveAlgorithms::Dijkstra,
+        SolveAlgorithms::AStar,
+    ];
+
+    #[test]
+    fn solves_a_corridor() {
+        let maze = Maze::parse(
+            "\
+#######
+#S    #
+##### #
+#X    #
+#######",
+        )
+        .unwrap();
+
+        let expected = "\
+#######
+#s * *#
+##### #
+#x * *#
+#######";
+
+        for algorithm in ALGORITHMS {
+            let steps = steps(&maze, &algorithm);
+
+            assert_eq!(
+                expected,
+                steps.last().unwrap().to_string(),
+                "{} found the wrong route",
+                algorithm.get_name()
+            );
+        }
+    }
+
+    #[test]
+    fn ties_leave_in_insertion_order() {
+        let mut frontier = Frontier::new(&SolveAlgorithms::Dijkstra);
+
+        frontier.push(7, 1, 0);
+        frontier.push(3, 1, 0);
+        assert_eq!(frontier.pop(), Some(7));
+
+        frontier.push(2, 1, 0);
+        frontier.push(1, 1, 0);
+
+        let order: Vec<usize> = std::iter::from_fn(|| frontier.pop()).collect();
+        assert_eq!(order, [3, 2, 1]);
+    }
+
+    #[test]
+    fn leaves_the_walls_alone() {
+        let maze = maze(&GenAlgorithms::Back, 8, 3);
+
+        for algorithm in ALGORITHMS {
+            for step in steps(&maze, &algorithm) {
+                assert!(
+                    step.cells()
+                        .iter()
+                        .zip(maze.cells())
+                        .all(|(a, b)| a.walls == b.walls),
+                    "{} changed a wall",
+                    algorithm.get_name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn route_has_shortest_length() {
+        let maze = maze(&GenAlgorithms::Kruskal, 10, 8);
+
+        // a perfect maze has only one route, so every solver must find it
+        let routes: Vec<usize> = ALGORITHMS
+            .iter()
+            .map(|algorithm| {
+                let solved = steps(&maze, algorithm).pop().unwrap();
+                solved
+                    .cells()
+                    .iter()
+                    .filter(|cell| cell.marker.is_route())
+                    .count()
+            })
+            .collect();
+
+        assert!(
+            routes.iter().all(|&r| r == routes[0] && r > 1),
+            "{routes:?}"
+        );
+    }
+}
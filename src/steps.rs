@@ -0,0 +1,565 @@
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    mem,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use crate::maze::{Cell, Maze};
+
+// A full copy is kept at least this often, so rebuilding a step never replays more
+// diffs than this.
+const KEYFRAME_INTERVAL: usize = 64;
+
+/// Holds the steps of a run as the cells that changed since the previous step.
+///
+/// Every [`KEYFRAME_INTERVAL`] steps, and whenever the grid changes shape, a full copy is
+/// kept instead so any step can be rebuilt from the keyframe before it. Memory grows
+/// with the number of changed cells rather than steps × area.
+#[derive(Default)]
+pub struct StepStore {
+    frames: Vec<Frame>,
+    // indices into `frames` of every keyframe, in order
+    keyframes: Vec<usize>,
+    last: Maze,
+}
+
+enum Frame {
+    Key(Maze),
+    Diff(Box<[(u32, Cell)]>),
+}
+
+impl StepStore {
+    pub fn new() -> StepStore {
+        StepStore::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn push(&mut self, step: Maze) {
+        let since_key = self.frames.len() - self.keyframes.last().copied().unwrap_or(0);
+        let reshaped = step.width() != self.last.width() || step.height() != self.last.height();
+
+        if self.frames.is_empty() || since_key >= KEYFRAME_INTERVAL || reshaped {
+            self.keyframes.push(self.frames.len());
+            self.frames.push(Frame::Key(step.clone()));
+        } else {
+            let diff = self
+                .last
+                .cells()
+                .iter()
+                .zip(step.cells())
+                .enumerate()
+                .filter(|(_, (old, new))| old != new)
+                .map(|(i, (_, &new))| (i as u32, new))
+                .collect();
+
+            self.frames.push(Frame::Diff(diff));
+        }
+
+        self.last = step;
+    }
+
+    /// Rebuilds step `index`. Panics if it is out of range.
+    pub fn get(&self, index: usize) -> Maze {
+        assert!(index < self.frames.len(), "step {index} is out of range");
+
+        let key = match self.keyframes.binary_search(&index) {
+            Ok(i) => self.keyframes[i],
+            Err(i) => self.keyframes[i - 1],
+        };
+
+        let Frame::Key(keyframe) = &self.frames[key] else {
+            unreachable!("keyframes only point at full copies");
+        };
+        let mut maze = keyframe.clone();
+
+        for frame in &self.frames[key + 1..=index] {
+            if let Frame::Diff(diff) = frame {
+                for &(i, cell) in diff.iter() {
+                    maze.set_cell(i as usize, cell);
+                }
+            }
+        }
+
+        maze
+    }
+
+    pub fn last(&self) -> Option<Maze> {
+        if self.frames.is_empty() {
+            None
+        } else {
+            Some(self.last.clone())
+        }
+    }
+}
+
+/// A step file read on demand.
+///
+/// Opening only starts a background scan for frame boundaries, so steps can be shown
+/// while the rest of a huge file is still being indexed. Each step is read from disk
+/// when it is asked for.
+pub struct StepFile {
+    path: PathBuf,
+    file: Mutex<File>,
+    // byte range and first line of every frame
+    index: Arc<Mutex<Vec<(u64, u64, usize)>>>,
+    indexed: Arc<AtomicBool>,
+    stop: Arc<AtomicBool>,
+    error: Arc<Mutex<Option<io::Error>>>,
+}
+
+impl StepFile {
+    pub fn open(path: &Path) -> io::Result<StepFile> {
+        let file = File::open(path)?;
+        let scan = File::open(path)?;
+
+        let index = Arc::new(Mutex::new(Vec::new()));
+        let indexed = Arc::new(AtomicBool::new(false));
+        let stop = Arc::new(AtomicBool::new(false));
+        let error = Arc::new(Mutex::new(None));
+
+        let scan_index = index.clone();
+        let scan_indexed = indexed.clone();
+        let scan_stop = stop.clone();
+        let scan_error = error.clone();
+
+        thread::spawn(move || {
+            let result = index_frames(scan, &scan_stop, |start, end, line| {
+                scan_index.lock().unwrap().push((start, end, line));
+            });
+
+            if let Err(err) = result {
+                *scan_error.lock().unwrap() = Some(err);
+            }
+
+            scan_indexed.store(true, Ordering::Release);
+        });
+
+        Ok(StepFile {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+            index,
+            indexed,
+            stop,
+            error,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The number of steps found so far.
+    pub fn len(&self) -> usize {
+        self.index.lock().unwrap().len()
+    }
+
+    /// Whether the whole file has been scanned.
+    pub fn is_indexed(&self) -> bool {
+        self.indexed.load(Ordering::Acquire)
+    }
+
+    /// Takes the error that stopped the scan, if any.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.error.lock().unwrap().take()
+    }
+
+    /// Reads and parses step `index`. A step that isn't a maze reports the line it is on
+    /// in the file.
+    pub fn get(&self, index: usize) -> io::Result<Maze> {
+        let (start, end, line) = self.index.lock().unwrap()[index];
+        let mut buf = vec![0; (end - start) as usize];
+
+        let mut file = self.file.lock().unwrap();
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut buf)?;
+
+        buf.retain(|&b| b != b'\r');
+
+        let step = String::from_utf8(buf)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        Maze::parse(&step).map_err(|mut err| {
+            err.line += line - 1;
+            io::Error::new(io::ErrorKind::InvalidData, err)
+        })
+    }
+}
+
+impl Drop for StepFile {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// Reports the byte range and first line (counting from 1) of every frame in `reader`,
+// the same frames `StepParser` finds. Carriage returns are left in the ranges.
+fn index_frames(
+    mut reader: impl Read,
+    stop: &AtomicBool,
+    mut on_frame: impl FnMut(u64, u64, usize),
+) -> io::Result<()> {
+    let mut buf = vec![0; 256 * 1024];
+    let mut pos = 0;
+    let mut line = 1;
+    let mut start = None;
+    let mut run_start = 0;
+    let mut newlines = 0;
+
+    loop {
+        if stop.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        for &byte in &buf[..n] {
+            match byte {
+                b'\n' => {
+                    if newlines == 0 {
+                        run_start = pos;
+                    }
+                    newlines += 1;
+
+                    if newlines == 2 {
+                        if let Some((start, first_line)) = start.take() {
+                            on_frame(start, run_start, first_line);
+                        }
+                    }
+
+                    line += 1;
+                }
+                b'\r' => {}
+                _ => {
+                    newlines = 0;
+                    start.get_or_insert((pos, line));
+                }
+            }
+
+            pos += 1;
+        }
+    }
+
+    if let Some((start, first_line)) = start {
+        on_frame(
+            start,
+            if newlines > 0 { run_start } else { pos },
+            first_line,
+        );
+    }
+
+    Ok(())
+}
+
+/// Where the steps being viewed live: collected in memory from a run, or read from a
+/// step file on disk.
+pub enum Steps {
+    Memory(StepStore),
+    File(StepFile),
+}
+
+impl Default for Steps {
+    fn default() -> Steps {
+        Steps::Memory(StepStore::new())
+    }
+}
+
+impl Steps {
+    pub fn len(&self) -> usize {
+        match self {
+            Steps::Memory(store) => store.len(),
+            Steps::File(file) => file.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> io::Result<Maze> {
+        match self {
+            Steps::Memory(store) => Ok(store.get(index)),
+            Steps::File(file) => file.get(index),
+        }
+    }
+
+    /// Adds a step from a run. A step file is swapped for an empty store first.
+    pub fn push(&mut self, step: Maze) {
+        if let Steps::File(_) = self {
+            *self = Steps::default();
+        }
+
+        if let Steps::Memory(store) = self {
+            store.push(step);
+        }
+    }
+
+    pub fn last(&self) -> Option<Maze> {
+        match self {
+            Steps::Memory(store) => store.last(),
+            Steps::File(file) => file.len().checked_sub(1).and_then(|i| file.get(i).ok()),
+        }
+    }
+
+    pub fn take(&mut self) -> Steps {
+        mem::take(self)
+    }
+}
+
+/// Splits a step stream into frames as it arrives.
+///
+/// Frames are separated by a blank line. Carriage returns are dropped so files written
+/// on Windows parse the same way.
+#[derive(Default)]
+pub struct StepParser {
+    buf: Vec<u8>,
+}
+
+impl StepParser {
+    pub fn new() -> StepParser {
+        StepParser::default()
+    }
+
+    /// Adds a chunk of the stream and returns every frame it completed.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buf.extend(chunk.iter().filter(|&&b| b != b'\r'));
+
+        let mut frames = Vec::new();
+        let mut start = 0;
+
+        while let Some(end) = find_separator(&self.buf[start..]) {
+            let frame = &self.buf[start..start + end];
+            // extra blank lines between frames are dropped
+            let frame = &frame[frame.iter().take_while(|&&b| b == b'\n').count()..];
+
+            if !frame.is_empty() {
+                frames.push(String::from_utf8_lossy(frame).into_owned());
+            }
+
+            start += end + 2;
+        }
+
+        self.buf.drain(..start);
+
+        frames
+    }
+
+    /// Returns the last frame once the stream has ended.
+    pub fn finish(self) -> Option<String> {
+        let frame = String::from_utf8_lossy(&self.buf);
+        let frame = frame.trim_matches('\n');
+
+        if frame.is_empty() {
+            None
+        } else {
+            Some(frame.to_string())
+        }
+    }
+}
+
+fn find_separator(buf: &[u8]) -> Option<usize> {
+    buf.windows(2).position(|w| w == b"\n\n")
+}
+
+#[cfg(test)]
+mod steps_tests {
+    use super::*;
+    use crate::maze::Marker;
+
+    #[test]
+    fn parse_steps_test() {
+        let test_str = "\
+#####
+# # #
+#####
+# # #
+#####
+
+#####
+# # #
+#####
+# # #
+#####
+
+#####
+# # #
+#####
+# # #
+#####";
+
+        let expected = vec![
+            "\
+#####
+# # #
+#####
+# # #
+#####",
+            "\
+#####
+# # #
+#####
+# # #
+#####",
+            "\
+#####
+# # #
+#####
+# # #
+#####",
+        ];
+
+        let mut parser = StepParser::new();
+        let mut steps = parser.feed(test_str.as_bytes());
+
+        steps.extend(parser.finish());
+
+        assert_eq!(
+            expected, steps,
+            "Maze steps did not parse the maze correctly"
+        );
+        assert_eq!(3, steps.len(), "Maze steps was not 3. Got {}", steps.len());
+    }
+
+    #[test]
+    fn frames_split_across_chunks() {
+        let mut parser = StepParser::new();
+        let stream = b"###\r\n# #\r\n###\r\n\r\n###\n#S#\n###\n\n###\n#X#\n###\n";
+        let mut frames = Vec::new();
+
+        // feed one byte at a time so every boundary gets split
+        for byte in stream {
+            frames.extend(parser.feed(&[*byte]));
+        }
+
+        assert_eq!(frames, ["###\n# #\n###", "###\n#S#\n###"]);
+        assert_eq!(parser.finish().as_deref(), Some("###\n#X#\n###"));
+    }
+
+    #[test]
+    fn store_rebuilds_every_step() {
+        let mut store = StepStore::new();
+        let mut pushed = Vec::new();
+
+        // long enough to pass a few keyframes, with a shape change in the middle
+        for i in 0..200 {
+            let width = if i < 100 { 3 } else { 4 };
+            let mut step = Maze::closed(width, 2);
+
+            step.set_marker(i % step.len(), Marker::Queued);
+            if i % 3 == 0 {
+                step.carve(0, 1);
+            }
+
+            store.push(step.clone());
+            pushed.push(step);
+        }
+
+        assert_eq!(store.len(), 200);
+        assert_eq!(store.last().as_ref(), pushed.last());
+
+        for (i, step) in pushed.iter().enumerate() {
+            assert_eq!(&store.get(i), step, "Step {i} was not rebuilt correctly");
+        }
+    }
+
+    #[test]
+    fn store_only_keeps_changes() {
+        let mut store = StepStore::new();
+        let step = Maze::closed(20, 20);
+
+        store.push(step.clone());
+        store.push(step.clone());
+
+        let Frame::Diff(diff) = &store.frames[1] else {
+            panic!("The second step should be a diff");
+        };
+
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn index_matches_parser() {
+        let stream = b"\n###\r\n# #\r\n###\r\n\r\n###\n#S#\n###\n\n\n\n###\n#X#\n###\n";
+        let mut frames = Vec::new();
+        let mut lines = Vec::new();
+
+        index_frames(&stream[..], &AtomicBool::new(false), |start, end, line| {
+            let frame: Vec<u8> = stream[start as usize..end as usize]
+                .iter()
+                .copied()
+                .filter(|&b| b != b'\r')
+                .collect();
+            frames.push(String::from_utf8(frame).unwrap());
+            lines.push(line);
+        })
+        .unwrap();
+
+        let mut parser = StepParser::new();
+        let mut parsed = parser.feed(stream);
+        parsed.extend(parser.finish());
+
+        assert_eq!(frames, ["###\n# #\n###", "###\n#S#\n###", "###\n#X#\n###"]);
+        assert_eq!(frames, parsed);
+        assert_eq!(lines, [2, 6, 12]);
+    }
+
+    #[test]
+    fn step_file_reads_on_demand() {
+        let path =
+            std::env::temp_dir().join(format!("step_file_test-{}.steps", std::process::id()));
+        let steps: Vec<Maze> = (0..500).map(|i| Maze::closed(1 + i % 9, 2)).collect();
+        let text: Vec<String> = steps.iter().map(Maze::to_string).collect();
+
+        std::fs::write(&path, text.join("\n\n")).unwrap();
+
+        let file = StepFile::open(&path).unwrap();
+
+        while !file.is_indexed() {
+            thread::yield_now();
+        }
+
+        assert!(file.take_error().is_none());
+        assert_eq!(file.len(), 500);
+        assert_eq!(file.get(0).unwrap(), steps[0]);
+        assert_eq!(file.get(321).unwrap(), steps[321]);
+        assert_eq!(file.get(499).unwrap(), steps[499]);
+
+        drop(file);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn bad_step_points_into_the_file() {
+        let path = std::env::temp_dir().join(format!("bad_step_test-{}.steps", std::process::id()));
+
+        std::fs::write(&path, "###\n# #\n###\n\n###\n#Z#\n###\n").unwrap();
+
+        let file = StepFile::open(&path).unwrap();
+
+        while !file.is_indexed() {
+            thread::yield_now();
+        }
+
+        assert!(file.get(0).is_ok());
+        assert_eq!(
+            file.get(1).unwrap_err().to_string(),
+            "line 6, column 2: unknown cell character 'Z'"
+        );
+
+        drop(file);
+        let _ = std::fs::remove_file(&path);
+    }
+}
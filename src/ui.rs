@@ -1,5 +1,11 @@
 mod algorithm_ui;
+mod browser_ui;
+mod error_ui;
 pub mod maze_ui;
+mod restore_ui;
+mod save_ui;
+mod seed_ui;
+mod setup_ui;
 mod size_ui;
 mod speed_ui;
 
@@ -12,7 +18,11 @@ use ratatui::{
 
 use crate::app::{App, CurrentScreen};
 
-use self::{algorithm_ui::algorithm_ui, maze_ui::maze_ui, size_ui::size_ui, speed_ui::speed_ui};
+use self::{
+    algorithm_ui::algorithm_ui, browser_ui::browser_ui, error_ui::error_ui, maze_ui::maze_ui,
+    restore_ui::restore_ui, save_ui::save_ui, seed_ui::seed_ui, setup_ui::setup_ui,
+    size_ui::size_ui, speed_ui::speed_ui,
+};
 
 pub fn ui(f: &mut Frame, app: &mut App) {
     let main_layout = Layout::vertical([Constraint::Length(3), Constraint::Min(5)]).split(f.size());
@@ -20,33 +30,74 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     let button_pannel = main_layout[0];
     let display_pannel = main_layout[1];
 
-    let size = if display_pannel.height > display_pannel.width {
-        display_pannel.width
-    } else {
-        display_pannel.height
-    };
-
-    app.set_max_size(usize::from((size - 1) / 2));
-
-    if app.maze == "" {
+    if app.maze.is_empty() {
         app.clear_maze();
     }
 
+    // the maze goes first, so the hints know whether it fits
+    match app.current_screen {
+        CurrentScreen::Main => maze_ui(f, display_pannel, app),
+        CurrentScreen::Size => size_ui(f, display_pannel, app),
+        CurrentScreen::Speed => speed_ui(f, display_pannel, app),
+        CurrentScreen::Seed => seed_ui(f, display_pannel, app),
+        CurrentScreen::Algorithm => algorithm_ui(f, display_pannel, app),
+        CurrentScreen::Setup => setup_ui(f, display_pannel, app),
+        CurrentScreen::Save => save_ui(f, display_pannel, app),
+        CurrentScreen::Restore => {
+            maze_ui(f, display_pannel, app);
+            restore_ui(f, display_pannel, app);
+        }
+        CurrentScreen::Open => {
+            maze_ui(f, display_pannel, app);
+
+            let style = Style::new().fg(app.default_color);
+            let highlight_style = Style::new().fg(app.highlight_fg).bg(app.highlight_bg);
+
+            if let Some(browser) = &mut app.browser {
+                browser_ui(f, display_pannel, browser, style, highlight_style);
+            }
+        }
+    };
+
+    let pan = match (app.viewport.scrolls(), app.viewport.follow) {
+        (false, _) => "",
+        (true, true) => " | Pan: H/J/K/L",
+        (true, false) => " | Pan: H/J/K/L | Follow: F",
+    };
+    let view = format!("View ({}): V{pan}", app.render_mode.get_name());
+
     let text = match app.current_screen {
+        _ if app.error.is_some() => "\nDismiss: Enter/Esc".to_string(),
+        CurrentScreen::Main if app.job.is_some() => {
+            let job = app.job.as_ref().unwrap();
+            let mut opts = format!("\n{} {} | Cancel: Esc\n{view}", job.label, job.spinner());
+
+            if app.can_step() {
+                opts += &format!(
+                    " | Run/Pause: R | Next step: Right | Previous step: Left | Step {}/{}",
+                    app.get_step_val(),
+                    app.maze_steps.len() - 1
+                );
+            }
+
+            opts
+        }
         CurrentScreen::Main => {
-            let mut opts =
-                "\nQuit: Q | Size settings: Z | Speed setting: P | Algorithms: A | Generate: G".to_string();
+            let mut opts = format!(
+                "{} | Seed: {} | Open: O | Save: Ctrl+S | Setup: U | Quit: Q\nSize settings: Z | Speed setting: P | Seed: E | Algorithms: A | Backend ({}): B | Generate: G",
+                app.gen_algorithm.get_name(),
+                app.get_seed_name(),
+                app.get_backend().name()
+            );
             if app.has_generated {
                 opts += &format!(
-                    " | Solve: S\nRun: R | Next step: Right | Previous step: Left | Step {}/{}",
+                    " | Solve: S\n{view} | Run/Pause: R | Next step: Right | Previous step: Left | Step {}/{}",
                     app.get_step_val(),
-                    if !app.has_generated {
-                        0
-                    } else {
-                        app.maze_steps.len() - 1
-                    }
+                    app.maze_steps.len().saturating_sub(1)
                 )
                 .to_string();
+            } else {
+                opts += &format!("\n{view}");
             }
 
             opts
@@ -55,7 +106,24 @@ pub fn ui(f: &mut Frame, app: &mut App) {
             "\nExit: Esc | Enter value: Enter | Width: W | Height: H".to_string()
         }
         CurrentScreen::Speed => "\nExit: Esc | Enter value: Enter".to_string(),
+        CurrentScreen::Seed if app.last_seed.is_some() => {
+            "\nExit: Esc | Enter value: Enter | Random: R | Current maze: C".to_string()
+        }
+        CurrentScreen::Seed => "\nExit: Esc | Enter value: Enter | Random: R".to_string(),
         CurrentScreen::Algorithm => "\nExit: Esc".to_string(),
+        CurrentScreen::Setup if app.setup.as_ref().is_some_and(|s| s.browser.is_some()) => {
+            "\nClose: Esc | Open: Enter | Parent directory: Backspace".to_string()
+        }
+        CurrentScreen::Open => {
+            "Open a maze (.mz) or step (.steps) file\nClose: Esc | Open: Enter | Parent directory: Backspace".to_string()
+        }
+        CurrentScreen::Restore => "\nRestore: Enter/Y | Start fresh: Esc/N".to_string(),
+        CurrentScreen::Save => {
+            "\nCancel: Esc | What to save: Tab | Save: Enter".to_string()
+        }
+        CurrentScreen::Setup => {
+            "Pick the tools to use, or leave a path empty for the built-in implementation\nCancel: Esc | Switch: Tab | Test: Enter | Browse: Ctrl+O | Built-in: Ctrl+B | Save: Ctrl+S".to_string()
+        }
     };
 
     let keybind_hints = Paragraph::new(text)
@@ -64,10 +132,7 @@ pub fn ui(f: &mut Frame, app: &mut App) {
 
     f.render_widget(keybind_hints, button_pannel);
 
-    match app.current_screen {
-        CurrentScreen::Main => maze_ui(f, display_pannel, app),
-        CurrentScreen::Size => size_ui(f, display_pannel, app),
-        CurrentScreen::Speed => speed_ui(f, display_pannel, app),
-        CurrentScreen::Algorithm => algorithm_ui(f, display_pannel, app),
-    };
+    if app.error.is_some() {
+        error_ui(f, display_pannel, app);
+    }
 }
@@ -0,0 +1,53 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    widgets::{Block, Clear, List},
+    Frame,
+};
+
+use crate::browser::FileBrowser;
+
+pub fn browser_ui(
+    f: &mut Frame,
+    browser_layout: Rect,
+    browser: &mut FileBrowser,
+    style: Style,
+    highlight_style: Style,
+) {
+    let width = browser_layout.width.min(70);
+    let height = browser_layout.height.min(20);
+
+    let layout = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(height),
+        Constraint::Min(0),
+    ])
+    .split(browser_layout)[1];
+
+    let layout = Layout::horizontal([
+        Constraint::Min(0),
+        Constraint::Length(width),
+        Constraint::Min(0),
+    ])
+    .split(layout)[1];
+
+    let items: Vec<String> = browser
+        .entries
+        .iter()
+        .map(|entry| {
+            if entry.is_dir {
+                format!("{}/", entry.name)
+            } else {
+                entry.name.clone()
+            }
+        })
+        .collect();
+
+    let display = List::new(items)
+        .block(Block::bordered().title(browser.dir.to_string_lossy().into_owned()))
+        .style(style)
+        .highlight_style(highlight_style);
+
+    f.render_widget(Clear, layout);
+    f.render_stateful_widget(display, layout, &mut browser.state);
+}
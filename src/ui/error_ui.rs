@@ -0,0 +1,52 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Block, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+pub fn error_ui(f: &mut Frame, error_layout: Rect, app: &mut App) {
+    let Some(error) = &app.error else {
+        return;
+    };
+
+    let width = error_layout.width.min(60);
+    let text_width = usize::from(width.saturating_sub(2)).max(1);
+
+    // rough line count after wrapping, enough to size the popup
+    let lines: usize = error
+        .lines()
+        .map(|line| line.chars().count().div_ceil(text_width).max(1))
+        .sum();
+    let height = u16::try_from(lines + 2)
+        .unwrap_or(u16::MAX)
+        .min(error_layout.height);
+
+    let layout = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(height),
+        Constraint::Min(0),
+    ])
+    .split(error_layout)[1];
+
+    let layout = Layout::horizontal([
+        Constraint::Min(0),
+        Constraint::Length(width),
+        Constraint::Min(0),
+    ])
+    .split(layout)[1];
+
+    let display = Paragraph::new(error.as_str())
+        .style(Style::new().fg(app.default_color))
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::bordered()
+                .title("Error")
+                .border_style(Style::new().fg(Color::Red)),
+        );
+
+    f.render_widget(Clear, layout);
+    f.render_widget(display, layout);
+}
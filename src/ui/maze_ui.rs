@@ -1,531 +1,1130 @@
-use ratatui::{layout::{Constraint, Layout, Rect}, style::Color, widgets::Widget, Frame};
-
-use crate::app::App;
-
+use std::str::FromStr;
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::{Buffer, Cell as BufferCell},
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Widget},
+    Frame,
+};
+
+use crate::{
+    app::App,
+    maze::{Direction, Marker, Maze, Walls},
+};
+
+/// Colors and characters the maze is drawn with.
 #[derive(Clone)]
 pub struct MazeView {
-    cells: Option<Vec<Cell>>,
-    height: usize,
-    width: usize,
-    default_color: Color,
-    observed_color: Color,
-    queued_color: Color,
-    path_color: Color,
-    route_color: Color,
+    pub default_color: Color,
+    pub observed_color: Color,
+    pub queued_color: Color,
+    pub path_color: Color,
+    pub route_color: Color,
+    pub glyphs: GlyphSet,
+}
+
+/// The characters box drawing uses for walls, corners, the path and the start and stop.
+/// Heavy walls with a light path is the default, ASCII works on any console and blocks
+/// draw the walls with the `#` of a maze file.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GlyphSet {
+    Ascii,
+    Light,
+    Heavy,
+    Double,
+    Rounded,
+    Blocks,
+}
+
+/// How the maze is drawn: with box-drawing characters, where a cell and the walls around
+/// it take 3x3 characters, with box-drawing characters twice as wide, so cells come out
+/// square, with half blocks, packing two rows of the `#` grid of the maze into one row of
+/// text, or with braille, packing 2x4 tiles of the grid into a character. `Auto` draws
+/// with box characters while the maze fits and braille once it doesn't.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RenderMode {
+    Auto,
+    Box,
+    Wide,
+    HalfBlocks,
+    Braille,
+}
+
+/// The part of a maze that is shown when it is larger than the window. Positions are
+/// text columns and rows of the drawn maze, so a cell and the walls around it are 3x3.
+pub struct Viewport {
+    pub x: usize,
+    pub y: usize,
+    /// Whether to keep the cells that changed last in view.
+    pub follow: bool,
+    focus: Option<(usize, usize)>,
+    drag: Option<(u16, u16)>,
+    // the size of the shown part and of the whole maze, and the mode, as last drawn
+    shown: (usize, usize),
+    size: (usize, usize),
+    mode: RenderMode,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-struct Cell {
-    up: bool,
-    down: bool,
-    left: bool,
-    right: bool,
-    queued: bool,
-    observed: bool,
-    path: bool,
-    route: bool,
-    start: bool,
-    stop: bool,
-    character: char,
+// Draws the part of `maze` from `offset` on with the colors of `view`.
+struct MazeWidget<'a> {
+    view: &'a MazeView,
+    maze: &'a Maze,
+    mode: RenderMode,
+    offset: (usize, usize),
 }
 
 pub fn maze_ui(f: &mut Frame, maze_layout: Rect, app: &mut App) {
-    let size = if maze_layout.height > maze_layout.width {
-        maze_layout.width
-    } else {
-        maze_layout.height
+    let maze = &app.maze;
+    let viewport = &mut app.viewport;
+
+    if maze.is_empty() {
+        return;
+    }
+
+    let mode = app.render_mode.resolve(maze, maze_layout);
+
+    let (width, height) = mode.size(maze);
+
+    // scrollbars take a row or column, which can make the other one needed as well
+    let fits = |room: u16, size: usize| size <= usize::from(room);
+    let mut vertical = !fits(maze_layout.height, height);
+    let horizontal = !fits(maze_layout.width.saturating_sub(u16::from(vertical)), width);
+    vertical = vertical
+        || !fits(
+            maze_layout.height.saturating_sub(u16::from(horizontal)),
+            height,
+        );
+
+    let area = Rect {
+        width: maze_layout.width.saturating_sub(u16::from(vertical)),
+        height: maze_layout.height.saturating_sub(u16::from(horizontal)),
+        ..maze_layout
     };
 
-    let layout = Layout::horizontal([
-        Constraint::Min(0),
-        Constraint::Max(size),
-        Constraint::Min(0),
-    ])
-    .split(maze_layout);
+    viewport.fit((width, height), area, mode);
 
-    let maze_layout = layout[1];
+    let widget = MazeWidget {
+        view: &app.maze_veiwer,
+        maze,
+        mode,
+        offset: (viewport.x, viewport.y),
+    };
 
-    let layout =
-        Layout::vertical([Constraint::Length(size), Constraint::Min(0)]).split(maze_layout);
+    f.render_widget(widget, area);
+
+    let style = Style::new().fg(app.default_color);
+
+    if vertical {
+        let mut state = ScrollbarState::new(height - usize::from(area.height) + 1)
+            .position(viewport.y)
+            .viewport_content_length(usize::from(area.height));
+
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .style(style)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .render(
+                Rect {
+                    height: area.height,
+                    ..maze_layout
+                },
+                f.buffer_mut(),
+                &mut state,
+            );
+    }
 
-    let maze_layout = layout[0];
+    if horizontal {
+        let mut state = ScrollbarState::new(width - usize::from(area.width) + 1)
+            .position(viewport.x)
+            .viewport_content_length(usize::from(area.width));
+
+        Scrollbar::new(ScrollbarOrientation::HorizontalBottom)
+            .style(style)
+            .begin_symbol(None)
+            .end_symbol(None)
+            .render(
+                Rect {
+                    width: area.width,
+                    ..maze_layout
+                },
+                f.buffer_mut(),
+                &mut state,
+            );
+    }
+}
 
-    app.maze_veiwer.load_maze(&app.maze);
+/// Draws all of `maze` the way the viewer shows it in `mode`, returning the cells of each
+/// row.
+pub fn render_maze(view: &MazeView, maze: &Maze, mode: RenderMode) -> Vec<Vec<BufferCell>> {
+    let (width, height) = mode.size(maze);
+    let width = u16::try_from(width).unwrap();
+
+    // a buffer holds at most u16::MAX cells, so larger mazes are drawn a band of rows at a
+    // time
+    let band = usize::from(u16::MAX / width);
+    let mut rows = Vec::with_capacity(height);
+
+    for top in (0..height).step_by(band) {
+        let area = Rect::new(0, 0, width, band.min(height - top) as u16);
+        let mut buf = Buffer::empty(area);
+
+        MazeWidget {
+            view,
+            maze,
+            mode,
+            offset: (0, top),
+        }
+        .render(area, &mut buf);
 
-    f.render_widget(app.maze_veiwer.clone(), maze_layout);
+        let band_rows = buf.content.chunks(usize::from(width));
+        rows.extend(band_rows.map(<[BufferCell]>::to_vec));
+    }
+
+    rows
 }
 
-impl Widget for MazeView {
+impl GlyphSet {
+    pub const ALL: [GlyphSet; 6] = [
+        GlyphSet::Ascii,
+        GlyphSet::Light,
+        GlyphSet::Heavy,
+        GlyphSet::Double,
+        GlyphSet::Rounded,
+        GlyphSet::Blocks,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            GlyphSet::Ascii => "ascii",
+            GlyphSet::Light => "light",
+            GlyphSet::Heavy => "heavy",
+            GlyphSet::Double => "double",
+            GlyphSet::Rounded => "rounded",
+            GlyphSet::Blocks => "blocks",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<GlyphSet> {
+        GlyphSet::ALL
+            .into_iter()
+            .find(|glyphs| glyphs.get_name().eq_ignore_ascii_case(name))
+    }
+
+    /// The wall or corner joining walls that go up, down, left and right.
+    pub fn wall(self, up: bool, down: bool, left: bool, right: bool) -> char {
+        let symbols = match self {
+            GlyphSet::Ascii => " ---|+++|+++|+++",
+            GlyphSet::Light => " ╶╴─╷┌┐┬╵└┘┴│├┤┼",
+            GlyphSet::Heavy => " ╺╸━╻┏┓┳╹┗┛┻┃┣┫╋",
+            GlyphSet::Double => " ═══║╔╗╦║╚╝╩║╠╣╬",
+            GlyphSet::Rounded => " ╶╴─╷╭╮┬╵╰╯┴│├┤┼",
+            GlyphSet::Blocks => " ###############",
+        };
+
+        pick(symbols, up, down, left, right)
+    }
+
+    /// The path through a cell, or an open wall, linked up, down, left and right.
+    pub fn path(self, up: bool, down: bool, left: bool, right: bool) -> char {
+        let symbols = match self {
+            GlyphSet::Ascii => " ---|+++|+++|+++",
+            GlyphSet::Rounded => " ╶╴─╷╭╮┬╵╰╯┴│├┤┼",
+            GlyphSet::Blocks => " ...............",
+            GlyphSet::Light | GlyphSet::Heavy | GlyphSet::Double => " ╶╴─╷┌┐┬╵└┘┴│├┤┼",
+        };
+
+        pick(symbols, up, down, left, right)
+    }
+
+    /// The start or stop `marker`, which looks different once the route reaches it.
+    pub fn marker(self, marker: Marker) -> char {
+        let symbols = match self {
+            GlyphSet::Ascii | GlyphSet::Blocks => "SsXx",
+            GlyphSet::Light | GlyphSet::Heavy | GlyphSet::Double | GlyphSet::Rounded => "▷▶□■",
+        };
+
+        let index = usize::from(marker.is_stop()) << 1 | usize::from(marker.is_route());
+
+        symbols.chars().nth(index).unwrap()
+    }
+}
+
+impl FromStr for GlyphSet {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<GlyphSet, ()> {
+        GlyphSet::from_name(name).ok_or(())
+    }
+}
+
+// The symbol for a combination of sides, from 16 of them ordered by the bits up = 8,
+// down = 4, left = 2 and right = 1.
+fn pick(symbols: &str, up: bool, down: bool, left: bool, right: bool) -> char {
+    let index =
+        usize::from(up) << 3 | usize::from(down) << 2 | usize::from(left) << 1 | usize::from(right);
+
+    symbols.chars().nth(index).unwrap()
+}
+
+impl RenderMode {
+    const ALL: [RenderMode; 5] = [
+        RenderMode::Auto,
+        RenderMode::Box,
+        RenderMode::Wide,
+        RenderMode::HalfBlocks,
+        RenderMode::Braille,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            RenderMode::Auto => "Auto",
+            RenderMode::Box => "Box",
+            RenderMode::Wide => "Wide",
+            RenderMode::HalfBlocks => "Half blocks",
+            RenderMode::Braille => "Braille",
+        }
+    }
+
+    pub fn next(self) -> RenderMode {
+        let next = RenderMode::ALL
+            .iter()
+            .position(|mode| *mode == self)
+            .unwrap()
+            + 1;
+        RenderMode::ALL[next % RenderMode::ALL.len()]
+    }
+
+    /// The columns and rows of text `maze` takes.
+    pub fn size(self, maze: &Maze) -> (usize, usize) {
+        let (columns, rows) = (2 * maze.width() + 1, 2 * maze.height() + 1);
+
+        match self {
+            RenderMode::Auto | RenderMode::Box => (columns, rows),
+            RenderMode::Wide => (2 * columns - 1, rows),
+            RenderMode::HalfBlocks => (columns, rows.div_ceil(2)),
+            RenderMode::Braille => (columns.div_ceil(2), rows.div_ceil(4)),
+        }
+    }
+
+    /// How many columns and rows of cells fit in `area`.
+    pub fn cells_in(self, area: Rect) -> (usize, usize) {
+        let (width, height) = (usize::from(area.width), usize::from(area.height));
+
+        // the columns and rows of the `#` grid that fit
+        let (columns, rows) = match self {
+            RenderMode::Auto | RenderMode::Box => (width, height),
+            RenderMode::Wide => (width.div_ceil(2), height),
+            RenderMode::HalfBlocks => (width, 2 * height),
+            RenderMode::Braille => (2 * width, 4 * height),
+        };
+
+        (columns.saturating_sub(1) / 2, rows.saturating_sub(1) / 2)
+    }
+
+    /// The mode `maze` is drawn with in `area`, which only differs for `Auto`.
+    pub fn resolve(self, maze: &Maze, area: Rect) -> RenderMode {
+        let (columns, rows) = RenderMode::Box.size(maze);
+
+        match self {
+            RenderMode::Auto
+                if columns <= usize::from(area.width) && rows <= usize::from(area.height) =>
+            {
+                RenderMode::Box
+            }
+            RenderMode::Auto => RenderMode::Braille,
+            mode => mode,
+        }
+    }
+
+    // Where a tile of the `#` grid ends up in the text.
+    fn locate(self, column: usize, row: usize) -> (usize, usize) {
+        match self {
+            RenderMode::Auto | RenderMode::Box => (column, row),
+            RenderMode::Wide => (2 * column, row),
+            RenderMode::HalfBlocks => (column, row / 2),
+            RenderMode::Braille => (column / 2, row / 4),
+        }
+    }
+}
+
+impl Viewport {
+    pub fn new() -> Viewport {
+        Viewport {
+            x: 0,
+            y: 0,
+            follow: true,
+            focus: None,
+            drag: None,
+            shown: (0, 0),
+            size: (0, 0),
+            mode: RenderMode::Box,
+        }
+    }
+
+    /// The mode the maze was last drawn with, with `Auto` resolved.
+    pub fn mode(&self) -> RenderMode {
+        self.mode
+    }
+
+    /// Whether only part of the maze was shown the last time it was drawn.
+    pub fn scrolls(&self) -> bool {
+        self.shown.0 < self.size.0 || self.shown.1 < self.size.1
+    }
+
+    /// Moves the view by `dx` columns and `dy` rows, and stops following the run.
+    pub fn pan(&mut self, dx: isize, dy: isize) {
+        self.x = self.x.saturating_add_signed(dx);
+        self.y = self.y.saturating_add_signed(dy);
+        self.follow = false;
+        self.clamp();
+    }
+
+    /// Dragging the maze pans it, and so does the mouse wheel.
+    pub fn mouse(&mut self, event: MouseEvent) {
+        let (column, row) = (event.column, event.row);
+
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => self.drag = Some((column, row)),
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some((x, y)) = self.drag.replace((column, row)) {
+                    self.pan(x as isize - column as isize, y as isize - row as isize);
+                }
+            }
+            MouseEventKind::Up(_) => self.drag = None,
+            MouseEventKind::ScrollDown => self.pan(0, 3),
+            MouseEventKind::ScrollUp => self.pan(0, -3),
+            MouseEventKind::ScrollRight => self.pan(3, 0),
+            MouseEventKind::ScrollLeft => self.pan(-3, 0),
+            _ => {}
+        }
+    }
+
+    /// Notes where `new` differs from `old`, the maze shown before it, so the view can
+    /// follow the run.
+    pub fn track(&mut self, old: &Maze, new: &Maze) {
+        if old.width() != new.width() || old.height() != new.height() {
+            self.focus = None;
+            return;
+        }
+
+        let (mut x, mut y, mut count) = (0, 0, 0);
+
+        for (i, (before, after)) in old.cells().iter().zip(new.cells()).enumerate() {
+            if before != after {
+                x += new.x(i);
+                y += new.y(i);
+                count += 1;
+            }
+        }
+
+        if let (Some(x), Some(y)) = (x.checked_div(count), y.checked_div(count)) {
+            self.focus = Some((2 * x + 1, 2 * y + 1));
+        }
+    }
+
+    // Keeps the view inside a maze of `size` drawn into `area`, and the focus away from
+    // its edges while following.
+    fn fit(&mut self, size: (usize, usize), area: Rect, mode: RenderMode) {
+        self.shown = (usize::from(area.width), usize::from(area.height));
+        self.size = size;
+        self.mode = mode;
+
+        if let Some((x, y)) = self.focus.filter(|_| self.follow) {
+            let (x, y) = mode.locate(x, y);
+            let (columns, rows) = self.shown;
+            self.x = self
+                .x
+                .min(x.saturating_sub(columns / 4))
+                .max((x + columns / 4 + 1).saturating_sub(columns));
+            self.y = self
+                .y
+                .min(y.saturating_sub(rows / 4))
+                .max((y + rows / 4 + 1).saturating_sub(rows));
+        }
+
+        self.clamp();
+    }
+
+    fn clamp(&mut self) {
+        self.x = self.x.min(self.size.0.saturating_sub(self.shown.0));
+        self.y = self.y.min(self.size.1.saturating_sub(self.shown.1));
+    }
+}
+
+// Where the text of a maze goes in a buffer. Positions outside the shown part are left out.
+// A wide screen puts every column two columns apart, with room in between to stretch
+// lines across.
+struct Screen<'a> {
+    buf: &'a mut Buffer,
+    area: Rect,
+    offset: (usize, usize),
+    wide: bool,
+}
+
+impl Screen<'_> {
+    fn get(&mut self, x: usize, y: usize) -> Option<&mut BufferCell> {
+        let x = if self.wide { 2 * x } else { x };
+        self.get_at(x, y)
+    }
+
+    // The column right of `x` on a wide screen.
+    fn get_after(&mut self, x: usize, y: usize) -> Option<&mut BufferCell> {
+        if self.wide {
+            self.get_at(2 * x + 1, y)
+        } else {
+            None
+        }
+    }
+
+    fn get_at(&mut self, x: usize, y: usize) -> Option<&mut BufferCell> {
+        let x = u16::try_from(x.checked_sub(self.offset.0)?).ok()?;
+        let y = u16::try_from(y.checked_sub(self.offset.1)?).ok()?;
+
+        (x < self.area.width && y < self.area.height)
+            .then(|| self.buf.get_mut(self.area.x + x, self.area.y + y))
+    }
+}
+
+impl Widget for MazeWidget<'_> {
     fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer)
     where
         Self: Sized,
     {
-        let str_width: u16 = u16::try_from(2 * self.width + 1).unwrap();
-        let str_height: u16 = u16::try_from(2 * self.height + 1).unwrap();
+        let maze = self.maze;
 
-        if str_width > area.width || str_height > area.height {
-            panic!("Maze was larger than viewer");
+        if maze.is_empty() {
+            return;
         }
 
-        if self.height == 0 || self.width == 0 {
-            panic!("Maze wasn't allocated");
+        let (str_width, str_height) = self.mode.size(maze);
+
+        // a maze smaller than the area is centered, a larger one is shown from the offset
+        let shown_width = u16::try_from(str_width).map_or(area.width, |w| w.min(area.width));
+        let shown_height = u16::try_from(str_height).map_or(area.height, |h| h.min(area.height));
+
+        let mut screen = Screen {
+            buf,
+            area: Rect::new(
+                area.x + (area.width - shown_width) / 2,
+                area.y + (area.height - shown_height) / 2,
+                shown_width,
+                shown_height,
+            ),
+            offset: self.offset,
+            wide: self.mode == RenderMode::Wide,
+        };
+
+        match self.mode {
+            RenderMode::Auto | RenderMode::Box | RenderMode::Wide => self.render_box(&mut screen),
+            RenderMode::HalfBlocks => self.render_half_blocks(&mut screen),
+            RenderMode::Braille => self.render_braille(&mut screen),
         }
+    }
+}
 
-        let x_gap = (area.width - str_width) / 2;
-        let y_gap = (area.height - str_height) / 2;
-
-        let start_x = area.x + x_gap;
-        let start_y = area.y + y_gap;
-
-        for (i, cell) in self.cells.as_ref().unwrap().iter().enumerate() {
-            let x = 2 * u16::try_from(i % self.width).unwrap() + 1;
-            let y = 2 * u16::try_from(i / self.width).unwrap() + 1;
-
-            let (path_up, path_down, path_left, path_right) =
-                get_paths(i, self.width, self.cells.as_ref().unwrap());
+impl MazeWidget<'_> {
+    fn render_box(&self, screen: &mut Screen) {
+        let maze = self.maze;
+        let view = self.view;
+        let glyphs = view.glyphs;
+        let horizontal_wall = glyphs.wall(false, false, true, true);
+        let vertical_wall = glyphs.wall(true, true, false, false);
+        let horizontal_path = glyphs.path(false, false, true, true);
+        let vertical_path = glyphs.path(true, true, false, false);
+
+        // only the cells with some of their text in view
+        let (x_offset, y_offset) = self.offset;
+        let (x_offset, width) = if screen.wide {
+            (x_offset / 2, usize::from(screen.area.width).div_ceil(2) + 1)
+        } else {
+            (x_offset, usize::from(screen.area.width))
+        };
+        let columns =
+            x_offset.saturating_sub(1) / 2..(x_offset + width).div_ceil(2).min(maze.width());
+        let rows = y_offset.saturating_sub(1) / 2
+            ..(y_offset + usize::from(screen.area.height))
+                .div_ceil(2)
+                .min(maze.height());
+
+        for i in rows.flat_map(|y| columns.clone().map(move |x| maze.index(x, y))) {
+            let cell = maze.cell(i);
+            let x = 2 * maze.x(i) + 1;
+            let y = 2 * maze.y(i) + 1;
+
+            let up = cell.walls.contains(Walls::UP);
+            let down = cell.walls.contains(Walls::DOWN);
+            let left = cell.walls.contains(Walls::LEFT);
+            let right = cell.walls.contains(Walls::RIGHT);
+
+            let (path_up, path_down, path_left, path_right) = get_links(maze, i, Marker::is_path);
             let (route_up, route_down, route_left, route_right) =
-                get_routes(i, self.width, self.cells.as_ref().unwrap());
+                get_links(maze, i, Marker::is_route);
 
             // upper wall
-            if cell.up || path_up || route_up {
-                buf.get_mut(start_x + x, start_y + y - 1)
-                    .set_char(if cell.up { '━' } else { '│' })
-                    .set_fg(if route_up {
-                        self.route_color
-                    } else if path_up {
-                        self.path_color
-                    } else {
-                        self.default_color
-                    });
+            if up || path_up || route_up {
+                if let Some(tile) = screen.get(x, y - 1) {
+                    tile.set_char(if up { horizontal_wall } else { vertical_path })
+                        .set_fg(if route_up {
+                            view.route_color
+                        } else if path_up {
+                            view.path_color
+                        } else {
+                            view.default_color
+                        });
+                }
             }
 
             // lower wall
-            if cell.down || path_down || route_down {
-                buf.get_mut(start_x + x, start_y + y + 1)
-                    .set_char(if cell.down { '━' } else { '│' })
-                    .set_fg(if route_down {
-                        self.route_color
-                    } else if path_down {
-                        self.path_color
-                    } else {
-                        self.default_color
-                    });
+            if down || path_down || route_down {
+                if let Some(tile) = screen.get(x, y + 1) {
+                    tile.set_char(if down { horizontal_wall } else { vertical_path })
+                        .set_fg(if route_down {
+                            view.route_color
+                        } else if path_down {
+                            view.path_color
+                        } else {
+                            view.default_color
+                        });
+                }
             }
 
             // left wall
-            if cell.left || path_left || route_left {
-                buf.get_mut(start_x + x - 1, start_y + y)
-                    .set_char(if cell.left { '┃' } else { '─' })
-                    .set_fg(if route_left {
-                        self.route_color
-                    } else if path_left {
-                        self.path_color
-                    } else {
-                        self.default_color
-                    });
+            if left || path_left || route_left {
+                if let Some(tile) = screen.get(x - 1, y) {
+                    tile.set_char(if left { vertical_wall } else { horizontal_path })
+                        .set_fg(if route_left {
+                            view.route_color
+                        } else if path_left {
+                            view.path_color
+                        } else {
+                            view.default_color
+                        });
+                }
             }
 
             // right wall
-            if cell.right || path_right || route_right {
-                buf.get_mut(start_x + x + 1, start_y + y)
-                    .set_char(if cell.right { '┃' } else { '─' })
+            if right || path_right || route_right {
+                if let Some(tile) = screen.get(x + 1, y) {
+                    tile.set_char(if right {
+                        vertical_wall
+                    } else {
+                        horizontal_path
+                    })
                     .set_fg(if route_right {
-                        self.route_color
+                        view.route_color
                     } else if path_right {
-                        self.path_color
+                        view.path_color
                     } else {
-                        self.default_color
+                        view.default_color
                     });
+                }
             }
 
-            if cell.start || cell.stop {
-                buf.get_mut(start_x + x, start_y + y)
-                    .set_char(cell.character)
-                    .set_fg(self.default_color);
-            } else if cell.route {
-                buf.get_mut(start_x + x, start_y + y)
-                    .set_char(get_path_symbol(
-                        path_up,
-                        path_down,
-                        path_left,
-                        path_right,
-                    ))
-                    .set_fg(self.route_color);
-            } else if cell.path {
-                buf.get_mut(start_x + x, start_y + y)
-                    .set_char(get_path_symbol(path_up, path_down, path_left, path_right))
-                    .set_fg(self.path_color);
-            } else if cell.observed {
-                buf.get_mut(start_x + x, start_y + y)
-                    .set_char(' ')
-                    .set_bg(self.observed_color);
-            } else if cell.queued {
-                buf.get_mut(start_x + x, start_y + y)
-                    .set_char(' ')
-                    .set_bg(self.queued_color);
+            let marker = cell.marker;
+
+            if let Some(tile) = screen.get(x, y) {
+                if marker.is_start() || marker.is_stop() {
+                    tile.set_char(glyphs.marker(marker))
+                        .set_fg(view.default_color);
+                } else if marker.is_route() {
+                    tile.set_char(glyphs.path(path_up, path_down, path_left, path_right))
+                        .set_fg(view.route_color);
+                } else if marker.is_path() {
+                    tile.set_char(glyphs.path(path_up, path_down, path_left, path_right))
+                        .set_fg(view.path_color);
+                } else if marker == Marker::Observed {
+                    tile.set_char(' ').set_bg(view.observed_color);
+                } else if marker.is_queued() {
+                    tile.set_char(' ').set_bg(view.queued_color);
+                }
             }
 
-            // corners wall
-            let (ulc, urc, llc, lrc) =
-                get_corner_symbols(i, self.width, self.height, self.cells.as_ref().unwrap());
-
-            buf.get_mut(start_x + x - 1, start_y + y - 1)
-                .set_char(ulc)
-                .set_fg(self.default_color);
-
-            buf.get_mut(start_x + x + 1, start_y + y - 1)
-                .set_char(urc)
-                .set_fg(self.default_color);
+            // stretch the walls above and below, and the path or filling, across a wide
+            // cell
+            for (wall, y) in [(up, y - 1), (down, y + 1)] {
+                for x in [x - 1, x] {
+                    if let Some(tile) = screen.get_after(x, y).filter(|_| wall) {
+                        tile.set_char(horizontal_wall).set_fg(view.default_color);
+                    }
+                }
+            }
 
-            buf.get_mut(start_x + x - 1, start_y + y + 1)
-                .set_char(llc)
-                .set_fg(self.default_color);
+            for (x, path, route) in [(x - 1, path_left, route_left), (x, path_right, route_right)] {
+                let Some(tile) = screen.get_after(x, y) else {
+                    continue;
+                };
+
+                if route {
+                    tile.set_char(horizontal_path).set_fg(view.route_color);
+                } else if path {
+                    tile.set_char(horizontal_path).set_fg(view.path_color);
+                } else if marker == Marker::Observed {
+                    tile.set_char(' ').set_bg(view.observed_color);
+                } else if marker.is_queued() && !marker.is_path() {
+                    tile.set_char(' ').set_bg(view.queued_color);
+                }
+            }
 
-            buf.get_mut(start_x + x + 1, start_y + y + 1)
-                .set_char(lrc)
-                .set_fg(self.default_color);
+            // corners wall
+            let (ulc, urc, llc, lrc) = get_corner_symbols(maze, i, glyphs);
+
+            for (x, y, symbol) in [
+                (x - 1, y - 1, ulc),
+                (x + 1, y - 1, urc),
+                (x - 1, y + 1, llc),
+                (x + 1, y + 1, lrc),
+            ] {
+                if let Some(tile) = screen.get(x, y) {
+                    tile.set_char(symbol).set_fg(view.default_color);
+                }
+            }
         }
     }
-}
 
-impl MazeView {
-    pub fn load_maze(&mut self, str: &str) {
-        // Smallest maze possible is 29 characters
-        if str.len() < 29 {
-            return;
+    fn render_half_blocks(&self, screen: &mut Screen) {
+        let (columns, rows) = (2 * self.maze.width() + 1, 2 * self.maze.height() + 1);
+        let (x_offset, y_offset) = self.offset;
+
+        for y in y_offset..(y_offset + usize::from(screen.area.height)) {
+            for x in x_offset..(x_offset + usize::from(screen.area.width)).min(columns) {
+                let top = get_tile_color(self.view, self.maze, x, 2 * y);
+                let bottom = (2 * y + 1 < rows)
+                    .then(|| get_tile_color(self.view, self.maze, x, 2 * y + 1))
+                    .flatten();
+
+                let Some(tile) = screen.get(x, y) else {
+                    continue;
+                };
+
+                match (top, bottom) {
+                    (None, None) => {}
+                    (Some(top), None) => {
+                        tile.set_char('▀').set_fg(top);
+                    }
+                    (None, Some(bottom)) => {
+                        tile.set_char('▄').set_fg(bottom);
+                    }
+                    (Some(top), Some(bottom)) if top == bottom => {
+                        tile.set_char('█').set_fg(top);
+                    }
+                    (Some(top), Some(bottom)) => {
+                        tile.set_char('▀').set_fg(top).set_bg(bottom);
+                    }
+                }
+            }
         }
+    }
 
-        let rows: Vec<&str> = str.split('\n').filter(|&s| s != "").collect();
-
-        self.height = (rows.len() - 1) / 2;
-        self.width = (rows[0].len() - 1) / 2;
-
-        let mut cells: Vec<Cell> = Vec::with_capacity(self.height * self.width);
-
-        for i in 0..self.height * self.width {
-            let y = 2 * (i / self.width) + 1;
-            let x = 2 * (i % self.width) + 1;
-            let up = rows[y - 1].chars().nth(x).unwrap() == '#';
-            let down = rows[y + 1].chars().nth(x).unwrap() == '#';
-            let left = rows[y].chars().nth(x - 1).unwrap() == '#';
-            let right = rows[y].chars().nth(x + 1).unwrap() == '#';
-            let character = rows[y].chars().nth(x).unwrap();
-            let path = match character {
-                '.' | '*' | 's' | 'x' | 'q' => true,
-                _ => false,
-            };
-            let route = match character {
-                '*' | 's' | 'x' | 'q' => true,
-                _ => false,
-            };
-            let observed = character == ':';
-            let queued = match character {
-                'Q' | 'q' => true,
-                _ => false,
-            };
-            let start = match character {
-                'S' | 's' => true,
-                _ => false,
-            };
-            let stop = match character {
-                'X' | 'x' => true,
-                _ => false,
-            };
-
-            let cell = Cell {
-                up,
-                down,
-                left,
-                right,
-                path,
-                route,
-                observed,
-                queued,
-                start,
-                stop,
-                character,
-            };
-
-            cells.push(cell);
+    fn render_braille(&self, screen: &mut Screen) {
+        // the bit of each dot of a braille character, by its row and column
+        const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+        let view = self.view;
+        let (columns, rows) = (2 * self.maze.width() + 1, 2 * self.maze.height() + 1);
+        let (x_offset, y_offset) = self.offset;
+
+        // a character has one color, the first of these any of its dots has
+        let tints = [
+            view.route_color,
+            view.path_color,
+            view.queued_color,
+            view.observed_color,
+        ];
+
+        for y in y_offset..(y_offset + usize::from(screen.area.height)) {
+            for x in x_offset..(x_offset + usize::from(screen.area.width)) {
+                let mut dots = 0;
+                let mut tint = tints.len();
+
+                for (dy, bits) in DOTS.iter().enumerate() {
+                    for (dx, bit) in bits.iter().enumerate() {
+                        let (column, row) = (2 * x + dx, 4 * y + dy);
+
+                        if column >= columns || row >= rows {
+                            continue;
+                        }
+
+                        if let Some(color) = get_tile_color(view, self.maze, column, row) {
+                            dots |= bit;
+                            tint = tints
+                                .iter()
+                                .position(|tint| *tint == color)
+                                .map_or(tint, |i| tint.min(i));
+                        }
+                    }
+                }
+
+                if let Some(tile) = screen.get(x, y).filter(|_| dots != 0) {
+                    tile.set_char(char::from_u32(0x2800 + dots).unwrap())
+                        .set_fg(tints.get(tint).copied().unwrap_or(view.default_color));
+                }
+            }
         }
-
-        self.cells = Some(cells);
     }
+}
 
+impl MazeView {
     pub fn new() -> MazeView {
         MazeView {
-            cells: None,
-            height: 0,
-            width: 0,
             default_color: Color::White,
             observed_color: Color::LightRed,
             queued_color: Color::Red,
             path_color: Color::LightBlue,
             route_color: Color::from_u32(0x00FFD580),
+            glyphs: GlyphSet::Heavy,
         }
     }
 }
 
-fn get_corner_symbols(
-    index: usize,
-    width: usize,
-    height: usize,
-    cells: &Vec<Cell>,
-) -> (char, char, char, char) {
-    let x = index % width;
-    let y = index / width;
+fn get_corner_symbols(maze: &Maze, index: usize, glyphs: GlyphSet) -> (char, char, char, char) {
+    // whether a neighbour, if there is one, has a wall
+    let wall = |from: Direction, direction: Direction| {
+        maze.neighbour(index, from)
+            .is_some_and(|cell| maze.has_wall(cell, direction))
+    };
 
-    let up = y > 0 && cells[index - width].left;
-    let down = cells[index].left;
-    let left = x > 0 && cells[index - 1].up;
-    let right = cells[index].up;
+    let up = wall(Direction::Up, Direction::Left);
+    let down = maze.has_wall(index, Direction::Left);
+    let left = wall(Direction::Left, Direction::Up);
+    let right = maze.has_wall(index, Direction::Up);
 
-    let ulc = get_wall_symbol(up, down, left, right);
+    let ulc = glyphs.wall(up, down, left, right);
 
-    let up = y > 0 && cells[index - width].right;
-    let down = cells[index].right;
-    let left = cells[index].up;
-    let right = x < width - 1 && cells[index + 1].up;
+    let up = wall(Direction::Up, Direction::Right);
+    let down = maze.has_wall(index, Direction::Right);
+    let left = maze.has_wall(index, Direction::Up);
+    let right = wall(Direction::Right, Direction::Up);
 
-    let urc = get_wall_symbol(up, down, left, right);
+    let urc = glyphs.wall(up, down, left, right);
 
-    let up = cells[index].left;
-    let down = y < height - 1 && cells[index + width].left;
-    let left = x > 0 && cells[index - 1].down;
-    let right = cells[index].down;
+    let up = maze.has_wall(index, Direction::Left);
+    let down = wall(Direction::Down, Direction::Left);
+    let left = wall(Direction::Left, Direction::Down);
+    let right = maze.has_wall(index, Direction::Down);
 
-    let llc = get_wall_symbol(up, down, left, right);
+    let llc = glyphs.wall(up, down, left, right);
 
-    let up = cells[index].right;
-    let down = y < height - 1 && cells[index + width].right;
-    let left = cells[index].down;
-    let right = x < width - 1 && cells[index + 1].down;
+    let up = maze.has_wall(index, Direction::Right);
+    let down = wall(Direction::Down, Direction::Right);
+    let left = maze.has_wall(index, Direction::Down);
+    let right = wall(Direction::Right, Direction::Down);
 
-    let lrc = get_wall_symbol(up, down, left, right);
+    let lrc = glyphs.wall(up, down, left, right);
 
     (ulc, urc, llc, lrc)
 }
 
-fn get_wall_symbol(up: bool, down: bool, left: bool, right: bool) -> char {
-    if up && down && left && right {
-        '╋'
-    } else if !up && down && left && right {
-        '┳'
-    } else if up && !down && left && right {
-        '┻'
-    } else if !up && !down && left && right {
-        '━'
-    } else if up && down && !left && right {
-        '┣'
-    } else if !up && down && !left && right {
-        '┏'
-    } else if up && !down && !left && right {
-        '┗'
-    } else if !up && !down && !left && right {
-        '╺'
-    } else if up && down && left && !right {
-        '┫'
-    } else if !up && down && left && !right {
-        '┓'
-    } else if up && !down && left && !right {
-        '┛'
-    } else if !up && !down && left && !right {
-        '╸'
-    } else if up && down && !left && !right {
-        '┃'
-    } else if !up && down && !left && !right {
-        '╻'
-    } else if up && !down && !left && !right {
-        '╹'
-    } else {
-        ' '
-    }
+/// Which open sides (up, down, left, right) of a cell lead to a neighbour that, like the
+/// cell, is on the line `on_line` picks out.
+pub fn get_links(
+    maze: &Maze,
+    index: usize,
+    on_line: fn(Marker) -> bool,
+) -> (bool, bool, bool, bool) {
+    let link = |direction| {
+        on_line(maze.marker(index))
+            && !maze.has_wall(index, direction)
+            && maze
+                .neighbour(index, direction)
+                .is_some_and(|cell| on_line(maze.marker(cell)))
+    };
+
+    (
+        link(Direction::Up),
+        link(Direction::Down),
+        link(Direction::Left),
+        link(Direction::Right),
+    )
 }
 
-fn get_paths(index: usize, width: usize, cells: &Vec<Cell>) -> (bool, bool, bool, bool) {
-    let up = !cells[index].up && cells[index].path && cells[index - width].path;
-    let down = !cells[index].down && cells[index].path && cells[index + width].path;
-    let left = !cells[index].left && cells[index].path && cells[index - 1].path;
-    let right = !cells[index].right && cells[index].path && cells[index + 1].path;
+/// The color of a tile of the `#` grid of `maze`, where walls and cells alternate, for
+/// renderers that draw every tile as a block. `None` leaves the background.
+pub fn get_tile_color(view: &MazeView, maze: &Maze, column: usize, row: usize) -> Option<Color> {
+    let (columns, rows) = (2 * maze.width() + 1, 2 * maze.height() + 1);
+
+    // the cell on one side of a wall tile, and the side the wall is on
+    let side = |column: usize, row: usize| {
+        if column % 2 == 1 {
+            let y = (row / 2).min(maze.height() - 1);
+            let direction = if row / 2 < maze.height() {
+                Direction::Up
+            } else {
+                Direction::Down
+            };
+            (maze.index(column / 2, y), direction)
+        } else {
+            let x = (column / 2).min(maze.width() - 1);
+            let direction = if column / 2 < maze.width() {
+                Direction::Left
+            } else {
+                Direction::Right
+            };
+            (maze.index(x, row / 2), direction)
+        }
+    };
 
-    (up, down, left, right)
-}
+    let fill = |marker: Marker| {
+        if marker == Marker::Observed {
+            Some(view.observed_color)
+        } else if marker.is_queued() {
+            Some(view.queued_color)
+        } else {
+            None
+        }
+    };
 
-fn get_routes(index: usize, width: usize, cells: &Vec<Cell>) -> (bool, bool, bool, bool) {
-    let up = !cells[index].up && cells[index].route && cells[index - width].route;
-    let down = !cells[index].down && cells[index].route && cells[index + width].route;
-    let left = !cells[index].left && cells[index].route && cells[index - 1].route;
-    let right = !cells[index].right && cells[index].route && cells[index + 1].route;
+    match (column % 2, row % 2) {
+        (1, 1) => {
+            let marker = maze.marker(maze.index(column / 2, row / 2));
+
+            if marker.is_start() || marker.is_stop() {
+                Some(view.default_color)
+            } else if marker.is_route() {
+                Some(view.route_color)
+            } else if marker.is_path() {
+                Some(view.path_color)
+            } else {
+                fill(marker)
+            }
+        }
+        (0, 0) => {
+            // a corner is drawn when a wall meets it
+            let wall = [
+                (column.wrapping_sub(1), row),
+                (column + 1, row),
+                (column, row.wrapping_sub(1)),
+                (column, row + 1),
+            ]
+            .into_iter()
+            .filter(|&(column, row)| column < columns && row < rows)
+            .any(|(column, row)| {
+                let (cell, direction) = side(column, row);
+                maze.has_wall(cell, direction)
+            });
 
-    (up, down, left, right)
-}
+            wall.then_some(view.default_color)
+        }
+        _ => {
+            let (cell, direction) = side(column, row);
 
-fn get_path_symbol(up: bool, down: bool, left: bool, right: bool) -> char {
-    if up && down && left && right {
-        '┼'
-    } else if !up && down && left && right {
-        '┬'
-    } else if up && !down && left && right {
-        '┴'
-    } else if !up && !down && left && right {
-        '─'
-    } else if up && down && !left && right {
-        '├'
-    } else if !up && down && !left && right {
-        '┌'
-    } else if up && !down && !left && right {
-        '└'
-    } else if !up && !down && !left && right {
-        '╶'
-    } else if up && down && left && !right {
-        '┤'
-    } else if !up && down && left && !right {
-        '┐'
-    } else if up && !down && left && !right {
-        '┘'
-    } else if !up && !down && left && !right {
-        '╴'
-    } else if up && down && !left && !right {
-        '│'
-    } else if !up && down && !left && !right {
-        '╷'
-    } else if up && !down && !left && !right {
-        '╵'
-    } else {
-        ' '
+            if maze.has_wall(cell, direction) {
+                return Some(view.default_color);
+            }
+
+            let (this, next) = (
+                maze.marker(cell),
+                maze.marker(maze.neighbour(cell, direction)?),
+            );
+
+            if this.is_route() && next.is_route() {
+                Some(view.route_color)
+            } else if this.is_path() && next.is_path() {
+                Some(view.path_color)
+            } else {
+                fill(this).filter(|_| fill(this) == fill(next))
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod maze_view_tests {
-    use std::iter::zip;
-
     use super::*;
 
-    #[test]
-    fn create_maze() {
-        let mut maze_view = MazeView::new();
+    // a route from the start round to the stop
+    const ROUTE: &str = "\
+#######
+#s * *#
+##### #
+#x * *#
+#######";
+
+    fn render(
+        view: &MazeView,
+        maze: &str,
+        mode: RenderMode,
+        size: (u16, u16),
+        offset: (usize, usize),
+    ) -> Buffer {
+        let area = Rect::new(0, 0, size.0, size.1);
+        let mut buf = Buffer::empty(area);
+
+        MazeWidget {
+            view,
+            maze: &Maze::parse(maze).unwrap(),
+            mode,
+            offset,
+        }
+        .render(area, &mut buf);
+
+        buf
+    }
+
+    fn rows(buf: &Buffer) -> Vec<String> {
+        (0..buf.area.height)
+            .map(|y| {
+                (0..buf.area.width)
+                    .map(|x| buf.get(x, y).symbol())
+                    .collect()
+            })
+            .collect()
+    }
 
-        maze_view.load_maze(
+    fn draw(maze: &str, mode: RenderMode, size: (u16, u16), offset: (usize, usize)) -> Vec<String> {
+        rows(&render(&MazeView::new(), maze, mode, size, offset))
+    }
+
+    #[test]
+    fn draws_walls() {
+        let rows = draw(
             "\
 #####
 # # #
 #####
 # # #
 #####",
+            RenderMode::Box,
+            (5, 5),
+            (0, 0),
         );
 
-        println!("Made it");
+        assert_eq!(rows, ["┏━┳━┓", "┃ ┃ ┃", "┣━╋━┫", "┃ ┃ ┃", "┗━┻━┛"]);
+    }
+
+    #[test]
+    fn draws_mazes_larger_than_a_buffer() {
+        let rows: Vec<String> =
+            render_maze(&MazeView::new(), &Maze::closed(130, 130), RenderMode::Box)
+                .iter()
+                .map(|row| row.iter().map(BufferCell::symbol).collect())
+                .collect();
+
+        assert_eq!(rows.len(), 261);
+        assert!(rows.iter().all(|row| row.chars().count() == 261));
+        assert!(rows[0].starts_with('┏') && rows[0].ends_with('┓'));
+        assert!(rows[260].starts_with('┗') && rows[260].ends_with('┛'));
+        // the rows past the first band are drawn like the ones before them
+        for y in 1..260 {
+            assert_eq!(rows[y], rows[2 - y % 2], "row {y}");
+        }
+    }
+
+    #[test]
+    fn draws_part_of_a_larger_maze() {
         assert_eq!(
-            maze_view.height, 2,
-            "Expected height to be 2, got {}",
-            maze_view.height
+            draw(ROUTE, RenderMode::Box, (4, 3), (2, 1)),
+            ["───┐", "━━╸│", "───┘"]
         );
+    }
+
+    #[test]
+    fn draws_wide_cells() {
         assert_eq!(
-            maze_view.width, 2,
-            "Expected height to be 2, got {}",
-            maze_view.width
+            draw(ROUTE, RenderMode::Wide, (13, 5), (0, 0)),
+            [
+                "┏━━━━━━━━━━━┓",
+                "┃ ▶───────┐ ┃",
+                "┣━━━━━━━╸ │ ┃",
+                "┃ ■───────┘ ┃",
+                "┗━━━━━━━━━━━┛"
+            ]
         );
+        assert_eq!(RenderMode::Wide.cells_in(Rect::new(0, 0, 13, 5)), (3, 2));
+    }
 
-        for cell in maze_view.cells.unwrap() {
-            assert_eq!(cell.up, true, "cell.up was false");
-            assert_eq!(cell.down, true, "cell.down was false");
-            assert_eq!(cell.left, true, "cell.left was false");
-            assert_eq!(cell.right, true, "cell.right was false");
-            assert_eq!(
-                cell.character, ' ',
-                "cell.character was '{}'",
-                cell.character
-            );
-            assert_eq!(cell.path, false, "cell.path was true");
-            assert_eq!(cell.route, false, "cell.route was true");
-            assert_eq!(cell.observed, false, "cell.observed was true");
-            assert_eq!(cell.queued, false, "cell.queued was true");
-            assert_eq!(cell.start, false, "cell.start was true");
-            assert_eq!(cell.stop, false, "cell.stop was true");
-        }
+    #[test]
+    fn draws_with_other_glyphs() {
+        let mut view = MazeView::new();
+        let mut draw = |glyphs| {
+            view.glyphs = glyphs;
+            rows(&render(&view, ROUTE, RenderMode::Box, (7, 5), (0, 0)))
+        };
+
+        assert_eq!(
+            draw(GlyphSet::Ascii),
+            ["+-----+", "|s---+|", "+----||", "|x---+|", "+-----+"]
+        );
+        assert_eq!(
+            draw(GlyphSet::Rounded),
+            ["╭─────╮", "│▶───╮│", "├───╴││", "│■───╯│", "╰─────╯"]
+        );
+        assert_eq!(
+            draw(GlyphSet::Blocks),
+            ["#######", "#s....#", "#####.#", "#x....#", "#######"]
+        );
+        assert_eq!("Double".parse(), Ok(GlyphSet::Double));
     }
 
     #[test]
-    fn symbol_detection() {
-        let mut maze_view = MazeView::new();
+    fn draws_half_blocks() {
+        let view = MazeView::new();
+        let buf = render(
+            &view,
+            "#####\n#Q  #\n#####",
+            RenderMode::HalfBlocks,
+            (5, 2),
+            (0, 0),
+        );
 
-        maze_view.load_maze(
-            "\
-#######
-#Q : x#
-# #####
-#. *  #
-##### #
-#s    #
-#######",
+        assert_eq!(rows(&buf), ["█▀▀▀█", "▀▀▀▀▀"]);
+        // the wall above the queued cell, with the cell below it
+        assert_eq!(buf.get(1, 0).fg, view.default_color);
+        assert_eq!(buf.get(1, 0).bg, view.queued_color);
+        assert_eq!(buf.get(2, 0).bg, Color::Reset);
+    }
+
+    #[test]
+    fn draws_braille_when_the_maze_does_not_fit() {
+        let maze = "#####\n#* *#\n#####";
+        let view = MazeView::new();
+
+        assert_eq!(
+            RenderMode::Auto.resolve(&Maze::parse(maze).unwrap(), Rect::new(0, 0, 5, 3)),
+            RenderMode::Box
+        );
+        assert_eq!(
+            RenderMode::Auto.resolve(&Maze::parse(maze).unwrap(), Rect::new(0, 0, 4, 3)),
+            RenderMode::Braille
         );
 
-        assert_eq!(maze_view.height, 3);
-        assert_eq!(maze_view.width, 3);
-
-        let mut expected: Vec<Cell> = Vec::with_capacity(maze_view.height * maze_view.width);
-
-        for _ in 0..(maze_view.height * maze_view.width) {
-            expected.push(Cell {
-                up: true,
-                down: true,
-                left: true,
-                right: true,
-                character: ' ',
-                path: false,
-                route: false,
-                observed: false,
-                queued: false,
-                start: false,
-                stop: false,
-            });
-        }
+        let buf = render(&view, maze, RenderMode::Braille, (3, 1), (0, 0));
 
-        expected[0].character = 'Q';
-        expected[0].right = false;
-        expected[0].down = false;
-        expected[0].queued = true;
-
-        expected[1].character = ':';
-        expected[1].right = false;
-        expected[1].left = false;
-        expected[1].observed = true;
-
-        expected[2].character = 'x';
-        expected[2].left = false;
-        expected[2].path = true;
-        expected[2].route = true;
-        expected[2].stop = true;
-
-        expected[3].character = '.';
-        expected[3].up = false;
-        expected[3].right = false;
-        expected[3].path = true;
-
-        expected[4].character = '*';
-        expected[4].left = false;
-        expected[4].right = false;
-        expected[4].path = true;
-        expected[4].route = true;
-
-        expected[5].left = false;
-        expected[5].down = false;
-
-        expected[6].character = 's';
-        expected[6].right = false;
-        expected[6].path = true;
-        expected[6].route = true;
-        expected[6].start = true;
-
-        expected[7].left = false;
-        expected[7].right = false;
-
-        expected[8].left = false;
-        expected[8].up = false;
-
-        for (i, (left, right)) in zip(maze_view.cells.unwrap(), expected).enumerate() {
-            assert_eq!(left, right, "{}: {:?} != {:?}", i, left, right);
-        }
+        assert_eq!(rows(&buf), ["⠿⠿⠇"]);
+        // characters with some of the route are tinted
+        assert_eq!(buf.get(0, 0).fg, view.route_color);
+        assert_eq!(buf.get(1, 0).fg, view.route_color);
+        assert_eq!(buf.get(2, 0).fg, view.default_color);
+    }
+
+    #[test]
+    fn follows_the_changes() {
+        let mut viewport = Viewport::new();
+        let old = Maze::closed(20, 20);
+        let mut new = old.clone();
+        new.set_marker(new.index(15, 18), Marker::Queued);
+
+        viewport.track(&old, &new);
+        viewport.fit((41, 41), Rect::new(0, 0, 10, 10), RenderMode::Box);
+
+        // cell (15, 18) is drawn at column 31 and row 37, and kept off the edges
+        assert_eq!((viewport.x, viewport.y), (24, 30));
+        assert!(viewport.scrolls());
+
+        viewport.pan(-30, 100);
+        assert_eq!((viewport.x, viewport.y), (0, 31));
+
+        // panning stops following
+        viewport.fit((41, 41), Rect::new(0, 0, 10, 10), RenderMode::Box);
+        assert_eq!((viewport.x, viewport.y), (0, 31));
+    }
+
+    #[test]
+    fn draws_the_route() {
+        assert_eq!(
+            draw(ROUTE, RenderMode::Box, (7, 5), (0, 0)),
+            ["┏━━━━━┓", "┃▶───┐┃", "┣━━━╸│┃", "┃■───┘┃", "┗━━━━━┛"]
+        );
     }
 }
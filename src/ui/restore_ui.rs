@@ -0,0 +1,63 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    widgets::{Block, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+pub fn restore_ui(f: &mut Frame, restore_layout: Rect, app: &mut App) {
+    let Some(session) = &app.session else {
+        return;
+    };
+
+    let mut text = "Pick up where the last session left off?\n".to_string();
+
+    match (&session.generator, &session.maze) {
+        (Some(generator), Some(maze)) => {
+            text += &format!("\n{generator} maze, {}x{}", maze.width(), maze.height());
+        }
+        (None, Some(maze)) => text += &format!("\nMaze, {}x{}", maze.width(), maze.height()),
+        _ => {}
+    }
+
+    if let Some(seed) = session.seed {
+        text += &format!(", seed {seed}");
+    }
+
+    if let Some(solver) = &session.solver {
+        text += &format!("\nSolved with {solver}");
+    }
+
+    if let Some(steps) = &session.steps {
+        text += &format!("\nStep {} of {}", session.step, steps.display());
+    }
+
+    let width = restore_layout.width.min(60);
+    let height = u16::try_from(text.lines().count() + 2)
+        .unwrap_or(u16::MAX)
+        .min(restore_layout.height);
+
+    let layout = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(height),
+        Constraint::Min(0),
+    ])
+    .split(restore_layout)[1];
+
+    let layout = Layout::horizontal([
+        Constraint::Min(0),
+        Constraint::Length(width),
+        Constraint::Min(0),
+    ])
+    .split(layout)[1];
+
+    let display = Paragraph::new(text)
+        .style(Style::new().fg(app.default_color))
+        .wrap(Wrap { trim: false })
+        .block(Block::bordered().title("Last session"));
+
+    f.render_widget(Clear, layout);
+    f.render_widget(display, layout);
+}
@@ -0,0 +1,59 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    widgets::{Block, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+
+pub fn save_ui(f: &mut Frame, save_layout: Rect, app: &mut App) {
+    let default_style = Style::new().fg(app.default_color);
+    let highlight_style = Style::new().fg(app.highlight_fg).bg(app.highlight_bg);
+
+    let Some(save) = &app.save else {
+        return;
+    };
+
+    let layout = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Length(2),
+        Constraint::Min(0),
+    ])
+    .split(save_layout);
+
+    let width = save_layout.width.min(70);
+    let center = |area| {
+        Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(width),
+            Constraint::Min(0),
+        ])
+        .split(area)[1]
+    };
+
+    let kind = Paragraph::new(save.kind.get_name())
+        .style(default_style)
+        .block(Block::bordered().title("Save"));
+
+    let path = Paragraph::new(save.path.as_str())
+        .style(default_style)
+        .block(
+            Block::bordered()
+                .title("File")
+                .border_style(highlight_style),
+        );
+
+    f.render_widget(kind, center(layout[1]));
+    f.render_widget(path, center(layout[2]));
+
+    if let Some(status) = &save.status {
+        let status = Paragraph::new(status.as_str())
+            .style(default_style.dim())
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(status, center(layout[3]));
+    }
+}
@@ -0,0 +1,91 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    app::App,
+    setup::{ProbeStatus, SetupField},
+};
+
+use super::browser_ui::browser_ui;
+
+pub fn setup_ui(f: &mut Frame, setup_layout: Rect, app: &mut App) {
+    let default_style = Style::new().fg(app.default_color);
+    let highlight_style = Style::new().fg(app.highlight_fg).bg(app.highlight_bg);
+
+    let Some(setup) = &mut app.setup else {
+        return;
+    };
+
+    let layout = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(5),
+        Constraint::Length(5),
+        Constraint::Min(0),
+    ])
+    .split(setup_layout);
+
+    let width = setup_layout.width.min(70);
+
+    let fields = [
+        (
+            layout[1],
+            SetupField::Generator,
+            "Generator (MazeCreator)",
+            &setup.gen_bin,
+            &setup.gen_status,
+        ),
+        (
+            layout[2],
+            SetupField::Solver,
+            "Solver (MazeSolver)",
+            &setup.solve_bin,
+            &setup.solve_status,
+        ),
+    ];
+
+    for (field_layout, field, title, bin, status) in fields {
+        let field_layout = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(width),
+            Constraint::Min(0),
+        ])
+        .split(field_layout)[1];
+
+        let field_layout =
+            Layout::vertical([Constraint::Length(3), Constraint::Length(2)]).split(field_layout);
+
+        let mut block = Block::bordered().title(title);
+
+        if setup.field == field {
+            block = block.border_style(highlight_style);
+        }
+
+        let input = if bin.is_empty() {
+            Paragraph::new("built-in").style(default_style.dim())
+        } else {
+            Paragraph::new(bin.as_str()).style(default_style)
+        };
+
+        let status = match status {
+            ProbeStatus::Untested => Paragraph::new("Not tested").style(default_style.dim()),
+            ProbeStatus::Passed if bin.is_empty() => {
+                Paragraph::new("Using the built-in implementation").style(default_style)
+            }
+            ProbeStatus::Passed => Paragraph::new("OK").style(Style::new().fg(Color::Green)),
+            ProbeStatus::Failed(reason) => {
+                Paragraph::new(reason.as_str()).style(Style::new().fg(Color::Red))
+            }
+        };
+
+        f.render_widget(input.block(block), field_layout[0]);
+        f.render_widget(status.wrap(Wrap { trim: true }), field_layout[1]);
+    }
+
+    if let Some(browser) = &mut setup.browser {
+        browser_ui(f, setup_layout, browser, default_style, highlight_style);
+    }
+}
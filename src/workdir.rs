@@ -0,0 +1,139 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Where the external tools read and write their files during a session.
+///
+/// Files without an explicit path live in a private temp directory that is removed on
+/// drop unless `keep` is set. Explicit paths are always left in place.
+pub struct WorkDir {
+    dir: PathBuf,
+    steps_path: Option<PathBuf>,
+    maze_path: Option<PathBuf>,
+    keep: bool,
+}
+
+impl WorkDir {
+    pub fn create(
+        steps_path: Option<PathBuf>,
+        maze_path: Option<PathBuf>,
+        keep: bool,
+    ) -> io::Result<WorkDir> {
+        Ok(WorkDir {
+            dir: create_private_dir(&env::temp_dir())?,
+            steps_path,
+            maze_path,
+            keep,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn keep(&self) -> bool {
+        self.keep
+    }
+
+    pub fn steps_path(&self) -> PathBuf {
+        self.steps_path
+            .clone()
+            .unwrap_or_else(|| self.dir.join("maze.steps"))
+    }
+
+    pub fn maze_path(&self) -> PathBuf {
+        self.maze_path
+            .clone()
+            .unwrap_or_else(|| self.dir.join("maze.mz"))
+    }
+
+    /// Whether the step file should still be there after a run.
+    pub fn keep_steps(&self) -> bool {
+        self.keep || self.steps_path.is_some()
+    }
+}
+
+impl Drop for WorkDir {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+// Makes a directory only this user can read, picking a new name if another session
+// already has one.
+fn create_private_dir(base: &Path) -> io::Result<PathBuf> {
+    let pid = process::id();
+
+    for attempt in 0..100 {
+        let dir = base.join(format!("MazeViewerTUI-{pid}-{attempt}"));
+        let mut builder = fs::DirBuilder::new();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+
+            builder.mode(0o700);
+        }
+
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "could not find a free temp directory name",
+    ))
+}
+
+#[cfg(test)]
+mod workdir_tests {
+    use super::*;
+
+    #[test]
+    fn removed_on_drop() {
+        let work_dir = WorkDir::create(None, None, false).unwrap();
+        let dir = work_dir.dir().to_path_buf();
+
+        fs::write(work_dir.steps_path(), "steps").unwrap();
+        assert!(work_dir.steps_path().starts_with(&dir));
+
+        drop(work_dir);
+
+        assert!(!dir.exists(), "The temp directory was not cleaned up");
+    }
+
+    #[test]
+    fn kept_when_asked() {
+        let work_dir = WorkDir::create(None, None, true).unwrap();
+        let dir = work_dir.dir().to_path_buf();
+
+        drop(work_dir);
+
+        assert!(dir.exists(), "The temp directory was removed");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn sessions_get_their_own_directory() {
+        let first = WorkDir::create(None, None, false).unwrap();
+        let second = WorkDir::create(None, None, false).unwrap();
+
+        assert_ne!(first.dir(), second.dir());
+    }
+
+    #[test]
+    fn explicit_paths_are_used() {
+        let work_dir = WorkDir::create(Some(PathBuf::from("my.steps")), None, false).unwrap();
+
+        assert_eq!(work_dir.steps_path(), PathBuf::from("my.steps"));
+        assert!(work_dir.maze_path().starts_with(work_dir.dir()));
+        assert!(work_dir.keep_steps());
+    }
+}